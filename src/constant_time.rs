@@ -0,0 +1,255 @@
+use std::time::Instant;
+
+// Arithmetic on little-endian arrays of L 64-bit limbs whose running time and memory accesses
+// only depend on L, and not on the values: choices are made with masks instead of branches
+
+// a + b + carry, with the carry out
+pub fn adc(a : u64, b : u64, carry : u64) -> (u64, u64){
+    let t = a as u128 + b as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+// a - b - borrow, with the borrow out (0 or 1)
+pub fn sbb(a : u64, b : u64, borrow : u64) -> (u64, u64){
+    let t = (a as u128).wrapping_sub(b as u128 + borrow as u128);
+    (t as u64, ((t >> 64) as u64) & 1)
+}
+
+// a + b*c + carry, with the carry out
+pub fn mac(a : u64, b : u64, c : u64, carry : u64) -> (u64, u64){
+    let t = a as u128 + (b as u128)*(c as u128) + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+// All ones when choice is 1, zero when it is 0
+pub fn mask(choice : u64) -> u64{
+    0u64.wrapping_sub(choice)
+}
+
+// a when mask is all ones, b when it is zero
+pub fn select<const L : usize>(mask : u64, a : &[u64; L], b : &[u64; L]) -> [u64; L]{
+    let mut result = [0; L];
+    for i in 0..L{
+        result[i] = b[i] ^ (mask & (a[i] ^ b[i]));
+    }
+    result
+}
+
+// Exchange a and b when mask is all ones
+pub fn cswap<const L : usize>(mask : u64, a : &mut [u64; L], b : &mut [u64; L]){
+    for i in 0..L{
+        let t = mask & (a[i] ^ b[i]);
+        a[i] ^= t;
+        b[i] ^= t;
+    }
+}
+
+// The little-endian limbs of bytes, which must fit in limbs
+pub fn limbs_from_le_bytes(bytes : &[u8], limbs : &mut [u64]){
+    assert!(bytes.len() <= 8*limbs.len());
+    limbs.iter_mut().for_each(|limb| *limb = 0);
+    for (i, byte) in bytes.iter().enumerate(){
+        limbs[i/8] |= (*byte as u64) << (8*(i%8));
+    }
+}
+
+pub fn limbs_to_le_bytes(limbs : &[u64], length : usize) -> Vec<u8>{
+    assert!(length <= 8*limbs.len());
+    (0..length).map(|i| (limbs[i/8] >> (8*(i%8))) as u8).collect()
+}
+
+// Arithmetic modulo an odd p (a prime for inv) on elements in Montgomery form aR mod p, with R = 2^(64m) for
+// the m limbs of p. Operands and results are slices of at least m limbs, of which only the first m are used
+#[derive(Debug, Clone, PartialEq)]
+pub struct MontgomeryDomain{
+    pub modulus : Vec<u64>,
+    inv : u64, // -p^-1 mod 2^64
+    r2 : Vec<u64>, // R^2 mod p
+    pub one : Vec<u64>, // R mod p
+    unit : Vec<u64>, // 1, whose product with aR is a
+}
+
+impl MontgomeryDomain{
+    pub fn new(modulus : &[u64]) -> MontgomeryDomain{
+        assert!(modulus[0] & 1 == 1, "the modulus of a Montgomery domain must be odd");
+        let m = modulus.iter().rposition(|limb| *limb != 0).unwrap() + 1;
+
+        // Newton iteration, each step doubles the number of correct low bits
+        let mut inv = 1u64;
+        for _i in 0..6{
+            inv = inv.wrapping_mul(2u64.wrapping_sub(modulus[0].wrapping_mul(inv)));
+        }
+
+        let mut domain = MontgomeryDomain{
+            modulus: modulus[..m].to_vec(),
+            inv: inv.wrapping_neg(),
+            r2: vec![0; m],
+            one: vec![0; m],
+            unit: vec![0; m],
+        };
+        domain.unit[0] = 1;
+        // R mod p and R^2 mod p by repeated doublings of 1
+        let mut x = vec![0; m];
+        x[0] = 1;
+        domain.reduce_once(&mut x, 0);
+        let mut doubled = vec![0; m];
+        for i in 0..128*m{
+            if i == 64*m{
+                domain.one = x.clone();
+            }
+            domain.add(&x, &x, &mut doubled);
+            x.copy_from_slice(&doubled);
+        }
+        domain.r2 = x;
+        domain
+    }
+
+    pub fn limbs(&self) -> usize{
+        self.modulus.len()
+    }
+
+    // x + 2^(64m)*high - p if it is non negative, x + 2^(64m)*high otherwise, for x + 2^(64m)*high < 2p
+    fn reduce_once(&self, x : &mut [u64], high : u64){
+        let mut borrow = 0;
+        for (x_i, p_i) in x.iter().zip(&self.modulus){
+            borrow = sbb(*x_i, *p_i, borrow).1;
+        }
+        let (_, borrow) = sbb(high, 0, borrow);
+        // subtract p when there was no borrow
+        let subtrahend = mask(borrow ^ 1);
+        let mut borrow = 0;
+        for (x_i, p_i) in x.iter_mut().zip(&self.modulus){
+            let (d, borrow_out) = sbb(*x_i, subtrahend & p_i, borrow);
+            *x_i = d;
+            borrow = borrow_out;
+        }
+    }
+
+    pub fn add(&self, a : &[u64], b : &[u64], result : &mut [u64]){
+        let mut carry = 0;
+        for i in 0..self.limbs(){
+            let (s, c) = adc(a[i], b[i], carry);
+            result[i] = s;
+            carry = c;
+        }
+        self.reduce_once(result, carry);
+    }
+
+    pub fn sub(&self, a : &[u64], b : &[u64], result : &mut [u64]){
+        let m = self.limbs();
+        let mut borrow = 0;
+        for i in 0..m{
+            let (d, borrow_out) = sbb(a[i], b[i], borrow);
+            result[i] = d;
+            borrow = borrow_out;
+        }
+        // add p back when the difference is negative
+        let addend = mask(borrow);
+        let mut carry = 0;
+        for (r_i, p_i) in result.iter_mut().zip(&self.modulus){
+            let (s, c) = adc(*r_i, addend & p_i, carry);
+            *r_i = s;
+            carry = c;
+        }
+    }
+
+    // REDC(a*b) = a*b*R^-1 mod p, with the coarsely integrated operand scanning method
+    pub fn mul(&self, a : &[u64], b : &[u64], result : &mut [u64]){
+        let (p, m) = (&self.modulus, self.limbs());
+        let t = &mut result[..m];
+        for limb in t.iter_mut(){
+            *limb = 0;
+        }
+        let mut t_high = 0;
+        for b_i in &b[..m]{
+            // t += a*b_i
+            let mut carry = 0;
+            for j in 0..m{
+                let (s, c) = mac(t[j], a[j], *b_i, carry);
+                t[j] = s;
+                carry = c;
+            }
+            let (s, t_higher) = adc(t_high, carry, 0);
+            t_high = s;
+
+            // t = (t + q*p)/2^64, where q is chosen so that the division is exact
+            let q = t[0].wrapping_mul(self.inv);
+            let (_, mut carry) = mac(t[0], q, p[0], 0);
+            for j in 1..m{
+                let (s, c) = mac(t[j], q, p[j], carry);
+                t[j-1] = s;
+                carry = c;
+            }
+            let (s, c) = adc(t_high, carry, 0);
+            t[m-1] = s;
+            t_high = t_higher + c;
+        }
+        self.reduce_once(t, t_high);
+    }
+
+    pub fn to_montgomery(&self, a : &[u64], result : &mut [u64]){
+        self.mul(a, &self.r2, result);
+    }
+
+    pub fn from_montgomery(&self, a : &[u64], result : &mut [u64]){
+        self.mul(a, &self.unit, result);
+    }
+
+    // Square and multiply always, over the 64m bits of the exponent
+    pub fn pow(&self, a : &[u64], exponent : &[u64], result : &mut [u64]){
+        let m = self.limbs();
+        let (mut square, mut product) = (vec![0; m], vec![0; m]);
+        result[..m].copy_from_slice(&self.one);
+        for i in (0..64*m).rev(){
+            self.mul(result, result, &mut square);
+            self.mul(&square, a, &mut product);
+            let bit = mask((exponent[i/64] >> (i%64)) & 1);
+            for j in 0..m{
+                result[j] = square[j] ^ (bit & (product[j] ^ square[j]));
+            }
+        }
+    }
+
+    // a^(p-2), the inverse of a non zero a
+    pub fn inv(&self, a : &[u64], result : &mut [u64]){
+        let mut exponent = self.modulus.clone();
+        let mut borrow = 2;
+        for limb in exponent.iter_mut(){
+            let (d, borrow_out) = sbb(*limb, borrow, 0);
+            *limb = d;
+            borrow = borrow_out;
+        }
+        self.pow(a, &exponent, result);
+    }
+}
+
+// Welch t statistic between the running times of f on two classes of inputs, in the spirit of dudect:
+// f(class) is timed measurements times with a random class, and the slowest tenth of the timings is
+// discarded. An absolute value above about 5 is a strong evidence that the running time depends on the class
+pub fn timing_t_statistic<F : FnMut(bool)>(measurements : usize, mut f : F) -> f64{
+    let mut timings = Vec::with_capacity(measurements);
+    for _i in 0..measurements{
+        let class = rand::random::<bool>();
+        let start = Instant::now();
+        f(class);
+        timings.push((class, start.elapsed().as_nanos() as f64));
+    }
+
+    let mut sorted : Vec<f64> = timings.iter().map(|(_, t)| *t).collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let threshold = sorted[9*measurements/10];
+
+    let statistics = |class : bool| {
+        let samples : Vec<f64> = timings.iter().filter(|(c, t)| *c == class && *t <= threshold).map(|(_, t)| *t).collect();
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>()/n;
+        let variance = samples.iter().map(|t| (t - mean)*(t - mean)).sum::<f64>()/(n - 1.0);
+        (n, mean, variance)
+    };
+    let (n_0, mean_0, variance_0) = statistics(false);
+    let (n_1, mean_1, variance_1) = statistics(true);
+    (mean_0 - mean_1)/(variance_0/n_0 + variance_1/n_1).sqrt()
+}
+
+#[cfg(all(test, feature = "gmp"))]
+mod test;
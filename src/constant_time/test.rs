@@ -0,0 +1,87 @@
+use super::*;
+
+use gmp::mpz::Mpz;
+
+use crate::ecdh::{x25519_constant_time, X25519_BASE_POINT};
+use crate::field::IntegerTrait;
+use crate::integers::{from_le_bytes, to_le_bytes, is_probable_prime};
+
+fn to_mpz<const L : usize>(limbs : &[u64; L]) -> Mpz{
+    from_le_bytes(&limbs_to_le_bytes(limbs, 8*L))
+}
+
+fn from_mpz<const L : usize>(n : &Mpz) -> [u64; L]{
+    let mut limbs = [0; L];
+    limbs_from_le_bytes(&to_le_bytes(n, 8*L), &mut limbs);
+    limbs
+}
+
+fn check_domain<const L : usize>(p : &Mpz){
+    let domain = MontgomeryDomain::new(&from_mpz::<L>(p));
+    let r = Mpz::from(1) << (64*L);
+    assert_eq!(domain.limbs(), L);
+    let operation = |f : &dyn Fn(&mut [u64])| {
+        let mut result = [0; L];
+        f(&mut result);
+        result
+    };
+    assert_eq!(to_mpz(&operation(&|r| r.copy_from_slice(&domain.one))), r.clone()%p);
+    for _i in 0..100{
+        let (a, b) = (Mpz::sample_uniform(&Mpz::from(0), p), Mpz::sample_uniform(&Mpz::from(0), p));
+        let (a_limbs, b_limbs) = (from_mpz::<L>(&a), from_mpz::<L>(&b));
+        assert_eq!(to_mpz(&operation(&|r| domain.add(&a_limbs, &b_limbs, r))), (a.clone() + b.clone())%p);
+        assert_eq!(to_mpz(&operation(&|r| domain.sub(&a_limbs, &b_limbs, r))), ((a.clone() - b.clone())%p + p.clone())%p);
+
+        let (a_mont, b_mont) = (operation(&|r| domain.to_montgomery(&a_limbs, r)), operation(&|r| domain.to_montgomery(&b_limbs, r)));
+        assert_eq!(to_mpz(&a_mont), (a.clone()*r.clone())%p);
+        let product = operation(&|r| domain.mul(&a_mont, &b_mont, r));
+        assert_eq!(to_mpz(&operation(&|r| domain.from_montgomery(&product, r))), (a.clone()*b.clone())%p);
+        if a != Mpz::from(0){
+            let inverse = operation(&|r| domain.inv(&a_mont, r));
+            assert_eq!(to_mpz(&operation(&|r| domain.from_montgomery(&inverse, r))), a.invert(p).unwrap());
+        }
+    }
+}
+
+#[test]
+fn montgomery_domain_arithmetic(){
+    let sample_prime = |base : Mpz| loop{
+        let p = base.clone() + Mpz::sample_uniform(&Mpz::from(0), &(Mpz::from(1) << 64));
+        if is_probable_prime(&p){
+            return p;
+        }
+    };
+    for _i in 0..10{
+        // moduli close to 2^(64L), to exercise the carries, and smaller ones
+        check_domain::<4>(&sample_prime((Mpz::from(1) << 256) - (Mpz::from(1) << 64)));
+        check_domain::<3>(&sample_prime(Mpz::from(1) << 130));
+    }
+    check_domain::<1>(&Mpz::from(10169));
+}
+
+#[test]
+fn select_and_cswap(){
+    let (mut a, mut b) = ([1u64, 2, 3], [4u64, 5, 6]);
+    assert_eq!(select(mask(1), &a, &b), a);
+    assert_eq!(select(mask(0), &a, &b), b);
+    cswap(mask(0), &mut a, &mut b);
+    assert_eq!((a, b), ([1, 2, 3], [4, 5, 6]));
+    cswap(mask(1), &mut a, &mut b);
+    assert_eq!((a, b), ([4, 5, 6], [1, 2, 3]));
+}
+
+// The running time of the ladder must not depend on the scalar: a scalar with few bits set is compared
+// with random ones. Timings are noisy on shared machines, hence the test is run on demand only
+#[test]
+#[ignore]
+fn dudect_x25519_constant_time(){
+    let fixed_scalar = [0u8; 32];
+    let random_scalars : Vec<[u8; 32]> = (0..1000).map(|_| rand::random()).collect();
+    let mut i = 0;
+    let t = timing_t_statistic(2000, |class| {
+        let scalar = if class { &random_scalars[i%1000] } else { &fixed_scalar };
+        i += 1;
+        x25519_constant_time(scalar, &X25519_BASE_POINT);
+    });
+    assert!(t.abs() < 5.0, "timing leakage detected, t = {}", t);
+}
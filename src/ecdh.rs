@@ -0,0 +1,127 @@
+use std::fmt::Display;
+
+use crate::field::Field;
+use crate::finite_fields::{FiniteField, Fp, IntegerAsType};
+use crate::elliptic_curves::EllipticCurve;
+use crate::elliptic_curves::fp_elliptic_curves::UnsignedProjPoint;
+use crate::integers::{from_le_bytes, to_le_bytes};
+use crate::standard_curves::{Integer, WideInteger, F25519, F448, curve25519, curve448};
+use crate::integers::fixed_width::{Int320, Int512};
+use crate::constant_time::{limbs_from_le_bytes, limbs_to_le_bytes};
+
+pub const X25519_BASE_POINT : [u8; 32] = [9, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                                          0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+pub const X448_BASE_POINT : [u8; 56] = [5, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                                        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+// Abscissa of [scalar]P on the Montgomery curve ell, where P has abscissa u, 0 for the infinite point
+pub fn montgomery_dh<K>(ell : &EllipticCurve<K>, scalar : K::Integer, u : K) -> K
+    where K : FiniteField + Display{
+    if !ell.is_montgomery(){
+        panic!("montgomery_dh must be used with curves in Montgomery form");
+    }
+    let q = ell.scalar_mult_unsigned(scalar, UnsignedProjPoint::finite_point(u));
+    if q.z == K::from_int(0){
+        K::from_int(0)
+    }else{
+        q.x/q.z
+    }
+}
+
+// RFC 7748 function on the Montgomery curve ell, shared by the X25519 and X448 functions over any field: the
+// ladder over bits bits of the decoded scalar, then x/z as x*z^(p-2), which gives 0 for the infinite point.
+// Over the constant-time fields below, the running time depends neither on the scalar nor on u
+fn rfc7748<K>(ell : &EllipticCurve<K>, scalar : &K::Integer, bits : usize, u : K) -> K
+    where K : FiniteField + Display{
+    let q = ell.montgomery_ladder(scalar, bits, UnsignedProjPoint::finite_point(u));
+    q.x*K::exp(q.z, K::cardinal() - K::Integer::from(2))
+}
+
+// RFC 7748 clamping of an X25519 scalar: the cofactor bits are cleared and the bit 254 is set
+fn clamp_scalar_25519(scalar : &[u8; 32]) -> [u8; 32]{
+    let mut k = *scalar;
+    k[0] &= 248;
+    k[31] &= 127;
+    k[31] |= 64;
+    k
+}
+
+pub fn decode_scalar_25519(scalar : &[u8; 32]) -> Integer{
+    from_le_bytes(&clamp_scalar_25519(scalar))
+}
+
+// RFC 7748 X25519 function
+pub fn x25519(scalar : &[u8; 32], u : &[u8; 32]) -> [u8; 32]{
+    let mut u = *u;
+    u[31] &= 127;
+    let result = rfc7748(&curve25519(), &decode_scalar_25519(scalar), 255, F25519::new(from_le_bytes(&u)));
+
+    let mut encoding = [0; 32];
+    encoding.copy_from_slice(&to_le_bytes(&result.to_integer(), 32));
+    encoding
+}
+
+// RFC 7748 clamping of an X448 scalar: the cofactor bits are cleared and the bit 447 is set
+fn clamp_scalar_448(scalar : &[u8; 56]) -> [u8; 56]{
+    let mut k = *scalar;
+    k[0] &= 252;
+    k[55] |= 128;
+    k
+}
+
+pub fn decode_scalar_448(scalar : &[u8; 56]) -> WideInteger{
+    from_le_bytes(&clamp_scalar_448(scalar))
+}
+
+// RFC 7748 X448 function
+pub fn x448(scalar : &[u8; 56], u : &[u8; 56]) -> [u8; 56]{
+    let result = rfc7748(&curve448(), &decode_scalar_448(scalar), 448, F448::new(from_le_bytes(u)));
+
+    let mut encoding = [0; 56];
+    encoding.copy_from_slice(&to_le_bytes(&result.to_integer(), 56));
+    encoding
+}
+
+// The fields of X25519 and X448 on fixed-width integers in Montgomery form, whose sums, differences, products
+// and conditional swaps run on masks instead of branches
+declare_finite_field!(ConstantTimeF25519, Int320, (Int320::from(1) << 255) - Int320::from(19), mconstant25519, montgomery);
+declare_finite_field!(ConstantTimeF448, Int512, (Int512::from(1) << 448) - (Int512::from(1) << 224) - Int512::from(1),
+                      mconstant448, montgomery);
+
+// X25519 function in constant time, with the same results as x25519
+pub fn x25519_constant_time(scalar : &[u8; 32], u : &[u8; 32]) -> [u8; 32]{
+    let k = clamp_scalar_25519(scalar);
+    let mut u = *u;
+    u[31] &= 127;
+
+    let ell = EllipticCurve::new_montgomery(ConstantTimeF25519::from_int(486662));
+    let mut limbs = [0; 5];
+    limbs_from_le_bytes(&u, &mut limbs);
+    let u = ConstantTimeF25519::new(Int320::from_limbs(limbs));
+    limbs_from_le_bytes(&k, &mut limbs);
+    let result = rfc7748(&ell, &Int320::from_limbs(limbs), 255, u);
+
+    let mut encoding = [0; 32];
+    encoding.copy_from_slice(&limbs_to_le_bytes(result.to_integer().limbs(), 32));
+    encoding
+}
+
+// X448 function in constant time, with the same results as x448
+pub fn x448_constant_time(scalar : &[u8; 56], u : &[u8; 56]) -> [u8; 56]{
+    let k = clamp_scalar_448(scalar);
+
+    let ell = EllipticCurve::new_montgomery(ConstantTimeF448::from_int(156326));
+    let mut limbs = [0; 8];
+    limbs_from_le_bytes(u, &mut limbs);
+    let u = ConstantTimeF448::new(Int512::from_limbs(limbs));
+    limbs_from_le_bytes(&k, &mut limbs);
+    let result = rfc7748(&ell, &Int512::from_limbs(limbs), 448, u);
+
+    let mut encoding = [0; 56];
+    encoding.copy_from_slice(&limbs_to_le_bytes(result.to_integer().limbs(), 56));
+    encoding
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,117 @@
+use super::*;
+
+fn hex_bytes(s : &str, bytes : &mut [u8]){
+    for (i, byte) in bytes.iter_mut().enumerate(){
+        *byte = u8::from_str_radix(&s[2*i..2*i+2], 16).unwrap();
+    }
+}
+
+fn hex_32(s : &str) -> [u8; 32]{
+    let mut bytes = [0; 32];
+    hex_bytes(s, &mut bytes);
+    bytes
+}
+
+fn hex_56(s : &str) -> [u8; 56]{
+    let mut bytes = [0; 56];
+    hex_bytes(s, &mut bytes);
+    bytes
+}
+
+#[test]
+fn x25519_test_vectors(){
+    assert_eq!(x25519(&hex_32("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4"),
+                      &hex_32("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c")),
+               hex_32("c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552"));
+
+    assert_eq!(x25519(&hex_32("4b66e9d4d1b4673c5ad22691957d6af5c11b6421e0ea01d42ca4169e7918ba0d"),
+                      &hex_32("e5210f12786811d3f4b7959d0538ae2c31dbe7106fc03c3efc4cd549c715a493")),
+               hex_32("95cbde9476e8907d7aade45cb4b873f88b595a68799fa152e6f8f7647aac7957"));
+}
+
+#[test]
+fn x25519_iterated(){
+    let mut k = X25519_BASE_POINT;
+    let mut u = X25519_BASE_POINT;
+    for i in 0..1000{
+        let r = x25519(&k, &u);
+        u = k;
+        k = r;
+        if i == 0{
+            assert_eq!(k, hex_32("422c8e7a6227d7bca1350b3e2bb7279f7897b87bb6854b783c60e80311ae3079"));
+        }
+    }
+    assert_eq!(k, hex_32("684cf59ba83309552800ef566f2f4d3c1c3887c49360e3875f2eb94d99532c51"));
+}
+
+#[test]
+fn x25519_diffie_hellman(){
+    let alice_private = hex_32("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+    let bob_private = hex_32("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb");
+
+    let alice_public = x25519(&alice_private, &X25519_BASE_POINT);
+    let bob_public = x25519(&bob_private, &X25519_BASE_POINT);
+    assert_eq!(alice_public, hex_32("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a"));
+    assert_eq!(bob_public, hex_32("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f"));
+
+    let shared = hex_32("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+    assert_eq!(x25519(&alice_private, &bob_public), shared);
+    assert_eq!(x25519(&bob_private, &alice_public), shared);
+}
+
+#[test]
+fn x448_test_vectors(){
+    assert_eq!(x448(&hex_56("3d262fddf9ec8e88495266fea19a34d28882acef045104d0d1aae121700a779c984c24f8cdd78fbff44943eba368f54b29259a4f1c600ad3"),
+                    &hex_56("06fce640fa3487bfda5f6cf2d5263f8aad88334cbd07437f020f08f9814dc031ddbdc38c19c6da2583fa5429db94ada18aa7a7fb4ef8a086")),
+               hex_56("ce3e4ff95a60dc6697da1db1d85e6afbdf79b50a2412d7546d5f239fe14fbaadeb445fc66a01b0779d98223961111e21766282f73dd96b6f"));
+
+    assert_eq!(x448(&hex_56("203d494428b8399352665ddca42f9de8fef600908e0d461cb021f8c538345dd77c3e4806e25f46d3315c44e0a5b4371282dd2c8d5be3095f"),
+                    &hex_56("0fbcc2f993cd56d3305b0b7d9e55d4c1a8fb5dbb52f8e9a1e9b6201b165d015894e56c4d3570bee52fe205e28a78b91cdfbde71ce8d157db")),
+               hex_56("884a02576239ff7a2f2f63b2db6a9ff37047ac13568e1e30fe63c4a7ad1b3ee3a5700df34321d62077e63633c575c1c954514e99da7c179d"));
+
+    assert_eq!(x448(&X448_BASE_POINT, &X448_BASE_POINT),
+               hex_56("3f482c8a9f19b01e6c46ee9711d9dc14fd4bf67af30765c2ae2b846a4d23a8cd0db897086239492caf350b51f833868b9bc2b3bca9cf4113"));
+}
+
+#[test]
+fn x448_diffie_hellman(){
+    let alice_private = hex_56("9a8f4925d1519f5775cf46b04b5800d4ee9ee8bae8bc5565d498c28dd9c9baf574a9419744897391006382a6f127ab1d9ac2d8c0a598726b");
+    let bob_private = hex_56("1c306a7ac2a0e2e0990b294470cba339e6453772b075811d8fad0d1d6927c120bb5ee8972b0d3e21374c9c921b09d1b0366f10b65173992d");
+
+    let alice_public = x448(&alice_private, &X448_BASE_POINT);
+    let bob_public = x448(&bob_private, &X448_BASE_POINT);
+    assert_eq!(alice_public, hex_56("9b08f7cc31b7e3e67d22d5aea121074a273bd2b83de09c63faa73d2c22c5d9bbc836647241d953d40c5b12da88120d53177f80e532c41fa0"));
+    assert_eq!(bob_public, hex_56("3eb7a829b0cd20f5bcfc0b599b6feccf6da4627107bdb0d4f345b43027d8b972fc3e34fb4232a13ca706dcb57aec3dae07bdc1c67bf33609"));
+
+    let shared = hex_56("07fff4181ac6cc95ec1c16a94a0f74d12da232ce40a77552281d282bb60c0b56fd2464c335543936521c24403085d59a449a5037514a879d");
+    assert_eq!(x448(&alice_private, &bob_public), shared);
+    assert_eq!(x448(&bob_private, &alice_public), shared);
+}
+
+#[test]
+fn constant_time_coincide(){
+    assert_eq!(x25519_constant_time(&hex_32("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4"),
+                                    &hex_32("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c")),
+               hex_32("c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552"));
+    assert_eq!(x448_constant_time(&X448_BASE_POINT, &X448_BASE_POINT),
+               hex_56("3f482c8a9f19b01e6c46ee9711d9dc14fd4bf67af30765c2ae2b846a4d23a8cd0db897086239492caf350b51f833868b9bc2b3bca9cf4113"));
+
+    for _i in 0..20{
+        let (k, u) : ([u8; 32], [u8; 32]) = (rand::random(), rand::random());
+        assert_eq!(x25519_constant_time(&k, &u), x25519(&k, &u));
+        // a non canonical u, above p
+        let mut u = [0xff; 32];
+        u[0] = rand::random::<u8>() | 0xf0;
+        assert_eq!(x25519_constant_time(&k, &u), x25519(&k, &u));
+
+        let mut k = [0; 56];
+        let mut u = [0; 56];
+        for i in 0..56{
+            k[i] = rand::random();
+            u[i] = rand::random();
+        }
+        assert_eq!(x448_constant_time(&k, &u), x448(&k, &u));
+    }
+    // the point of order 2 gives 0
+    assert_eq!(x25519_constant_time(&X25519_BASE_POINT, &[0; 32]), [0; 32]);
+}
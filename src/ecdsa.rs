@@ -0,0 +1,202 @@
+use std::fmt::Display;
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+use crate::field::IntegerTrait;
+use crate::finite_fields::FiniteField;
+use crate::elliptic_curves::ProjKPoint;
+use crate::integers::{modulo, mod_inverse, from_be_bytes, to_be_bytes, bit_length};
+use crate::standard_curves::DomainParameters;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature<Integer>{
+    pub r : Integer,
+    pub s : Integer,
+}
+
+// Leftmost bit_length(order) bits of bytes
+fn bits2int<Integer : IntegerTrait>(bytes : &[u8], order : &Integer) -> Integer{
+    let n = from_be_bytes(bytes);
+    let qlen = bit_length(order);
+    if 8*bytes.len() > qlen{
+        n >> (8*bytes.len() - qlen)
+    }else{
+        n
+    }
+}
+
+fn hmac_sha256(key : &[u8], data : &[&[u8]]) -> Vec<u8>{
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).unwrap();
+    for d in data{
+        mac.update(d);
+    }
+    mac.finalize().into_bytes().to_vec()
+}
+
+// State (K, V) of the RFC 6979 generator, with HMAC-SHA256, whose successive candidates are drawn
+// until one gives a valid signature
+struct NonceGenerator<'a, Integer>{
+    order : &'a Integer,
+    rlen : usize,
+    k : Vec<u8>,
+    v : Vec<u8>,
+    drawn : bool,
+}
+
+impl<'a, Integer : IntegerTrait> NonceGenerator<'a, Integer>{
+    fn new(order : &'a Integer, private_key : &Integer, digest : &[u8]) -> NonceGenerator<'a, Integer>{
+        let rlen = bit_length(order).div_ceil(8);
+        let x = to_be_bytes(private_key, rlen);
+        let h = to_be_bytes(&modulo(bits2int(digest, order), order), rlen);
+
+        let v = vec![1u8; 32];
+        let k = vec![0u8; 32];
+        let k = hmac_sha256(&k, &[&v, &[0], &x, &h]);
+        let v = hmac_sha256(&k, &[&v]);
+        let k = hmac_sha256(&k, &[&v, &[1], &x, &h]);
+        let v = hmac_sha256(&k, &[&v]);
+        NonceGenerator{ order, rlen, k, v, drawn: false }
+    }
+
+    // Next candidate in [1, order), after the update of step h.3 when a candidate was already drawn
+    fn next_nonce(&mut self) -> Integer{
+        loop{
+            if self.drawn{
+                self.k = hmac_sha256(&self.k, &[&self.v, &[0]]);
+                self.v = hmac_sha256(&self.k, &[&self.v]);
+            }
+            self.drawn = true;
+
+            let mut t = Vec::new();
+            while t.len() < self.rlen{
+                self.v = hmac_sha256(&self.k, &[&self.v]);
+                t.extend_from_slice(&self.v);
+            }
+            let nonce = bits2int(&t, self.order);
+            if Integer::from(1) <= nonce && &nonce < self.order{
+                return nonce;
+            }
+        }
+    }
+}
+
+// RFC 6979 deterministic nonce, with HMAC-SHA256
+pub fn deterministic_nonce<Integer : IntegerTrait>(order : &Integer, private_key : &Integer, digest : &[u8]) -> Integer{
+    NonceGenerator::new(order, private_key, digest).next_nonce()
+}
+
+pub fn public_key<K>(parameters : &DomainParameters<K>, private_key : &K::Integer) -> ProjKPoint<K>
+    where K : FiniteField + Display{
+    parameters.curve.scalar_mult(private_key.clone(), parameters.generator.clone())
+}
+
+pub fn generate_keypair<K>(parameters : &DomainParameters<K>) -> (K::Integer, ProjKPoint<K>)
+    where K : FiniteField + Display{
+    let private_key = K::Integer::sample_uniform(&K::Integer::from(1), &parameters.order);
+    let public_key = public_key(parameters, &private_key);
+    (private_key, public_key)
+}
+
+// Sign a message digest, and return the signature with its recovery id
+pub fn sign_digest<K>(parameters : &DomainParameters<K>, private_key : &K::Integer, digest : &[u8]) -> (Signature<K::Integer>, u8)
+    where K : FiniteField + Display{
+    let n = &parameters.order;
+    let e = modulo(bits2int(digest, n), n);
+    let private_key = modulo(private_key.clone(), n);
+    let mut nonces = NonceGenerator::new(n, &private_key, digest);
+
+    loop{
+        let k = nonces.next_nonce();
+        let (x, y) = match parameters.curve.scalar_mult(k.clone(), parameters.generator.clone()){
+            ProjKPoint::FinPoint(x, y) => (x.to_integer(), y.to_integer()),
+            ProjKPoint::InfPoint => panic!("ECDSA must be used with a generator of order n"),
+        };
+        let r = modulo(x.clone(), n);
+        let s = mod_inverse(k, n).unwrap().mul_mod(&e.add_mod(&r.mul_mod(&private_key, n), n), n);
+        if r == K::Integer::from(0) || s == K::Integer::from(0){ // RFC 6979 section 3.4: next candidate
+            continue;
+        }
+
+        // bit 0: parity of the ordinate of [k]G, bit 1: whether its abscissa exceeds n
+        let mut recovery_id = if y%2 == K::Integer::from(0) { 0 } else { 1 };
+        if &x >= n{
+            recovery_id += 2;
+        }
+        return (Signature{ r, s }, recovery_id);
+    }
+}
+
+// ECDSA signature of message hashed with SHA-256, with RFC 6979 nonce
+pub fn sign<K>(parameters : &DomainParameters<K>, private_key : &K::Integer, message : &[u8]) -> Signature<K::Integer>
+    where K : FiniteField + Display{
+    sign_digest(parameters, private_key, &Sha256::digest(message)).0
+}
+
+pub fn verify_digest<K>(parameters : &DomainParameters<K>, public_key : &ProjKPoint<K>, digest : &[u8], signature : &Signature<K::Integer>) -> bool
+    where K : FiniteField + Display{
+    let n = &parameters.order;
+    let Signature{ r, s } = signature;
+    let one = K::Integer::from(1);
+    if r < &one || r >= n || s < &one || s >= n{
+        return false;
+    }
+    if public_key == &ProjKPoint::InfPoint || !parameters.curve.is_on_curve(public_key){
+        return false;
+    }
+
+    let w = mod_inverse(s.clone(), n).unwrap();
+    let u1 = modulo(bits2int(digest, n), n).mul_mod(&w, n);
+    let u2 = r.mul_mod(&w, n);
+    let ell = &parameters.curve;
+    match ell.add_points(ell.scalar_mult(u1, parameters.generator.clone()), ell.scalar_mult(u2, public_key.clone())){
+        ProjKPoint::FinPoint(x, _) => &modulo(x.to_integer(), n) == r,
+        ProjKPoint::InfPoint => false,
+    }
+}
+
+pub fn verify<K>(parameters : &DomainParameters<K>, public_key : &ProjKPoint<K>, message : &[u8], signature : &Signature<K::Integer>) -> bool
+    where K : FiniteField + Display{
+    verify_digest(parameters, public_key, &Sha256::digest(message), signature)
+}
+
+// Public key recovery from a signature of a digest and its recovery id, None if no such key exists
+pub fn recover_public_key<K>(parameters : &DomainParameters<K>, digest : &[u8], signature : &Signature<K::Integer>, recovery_id : u8)
+    -> Option<ProjKPoint<K>>
+    where K : FiniteField + Display{
+    let n = &parameters.order;
+    let ell = &parameters.curve;
+    let Signature{ r, s } = signature;
+
+    let x_int = if recovery_id & 2 == 0 { r.clone() } else { r.clone() + n.clone() };
+    if x_int >= K::cardinal(){
+        return None;
+    }
+    let x = K::new(x_int);
+    let f = x.clone()*x.clone()*x.clone() + ell.a_2.clone()*x.clone()*x.clone() + ell.a_4.clone()*x.clone() + ell.a_6.clone();
+    if f.legendre_symbol() == -1{
+        return None;
+    }
+    let mut y = f.square_root();
+    if (y.to_integer()%2 == K::Integer::from(0)) != (recovery_id & 1 == 0){
+        y = -y;
+    }
+    let big_r = ProjKPoint::FinPoint(x, y);
+    if !ell.is_on_curve(&big_r) || ell.scalar_mult(n.clone(), big_r.clone()) != ProjKPoint::InfPoint{
+        return None;
+    }
+
+    // Q = r^-1 (sR - eG)
+    let r_inv = mod_inverse(r.clone(), n)?;
+    let u1 = modulo(-bits2int(digest, n), n).mul_mod(&r_inv, n);
+    let u2 = modulo(s.clone(), n).mul_mod(&r_inv, n);
+    let public_key = ell.add_points(ell.scalar_mult(u1, parameters.generator.clone()), ell.scalar_mult(u2, big_r));
+    if public_key == ProjKPoint::InfPoint{
+        None
+    }else{
+        Some(public_key)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,147 @@
+use super::*;
+
+use crate::field::Field;
+use crate::standard_curves::*;
+use crate::finite_fields::*;
+use crate::elliptic_curves::EllipticCurve;
+use crate::integers::{factor, is_probable_prime};
+
+declare_finite_field!(GL10169, Integer, Integer::from(10169), m10169);
+
+fn hex(s : &str) -> Integer{
+    Integer::from_str_radix(s, 16).unwrap()
+}
+
+fn wide_hex(s : &str) -> WideInteger{
+    WideInteger::from_str_radix(s, 16).unwrap()
+}
+
+const RFC6979_PRIVATE_KEY : &str = "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721";
+
+#[test]
+fn p256_rfc6979_test_vectors(){
+    let parameters = p256();
+    let private_key = hex(RFC6979_PRIVATE_KEY);
+    assert_eq!(public_key(&parameters, &private_key),
+               ProjKPoint::FinPoint(FP256::new(hex("60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6")),
+                                    FP256::new(hex("7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299"))));
+
+    assert_eq!(deterministic_nonce(&parameters.order, &private_key, &Sha256::digest(b"sample")),
+               hex("a6e3c57dd01abe90086538398355dd4c3b17aa873382b0f24d6129493d8aad60"));
+    assert_eq!(sign(&parameters, &private_key, b"sample"), Signature{
+        r: hex("efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716"),
+        s: hex("f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"),
+    });
+    assert_eq!(sign(&parameters, &private_key, b"test"), Signature{
+        r: hex("f1abb023518351cd71d881567b1ea663ed3efcf6c5132b354f28d3b0b7d38367"),
+        s: hex("019f4113742a2b14bd25926b49c649155f267e60d3814b4c0cc84250e46f0083"),
+    });
+}
+
+// RFC 6979 A.2.6, with SHA-256
+#[test]
+fn p384_rfc6979_test_vectors(){
+    let parameters = p384();
+    assert!(parameters.curve.is_on_curve(&parameters.generator));
+    assert_eq!(parameters.curve.scalar_mult(parameters.order.clone(), parameters.generator.clone()), ProjKPoint::InfPoint);
+
+    let private_key = wide_hex("6b9d3dad2e1b8c1c05b19875b6659f4de23c3b667bf297ba9aa47740787137d896d5724e4c70a825f872c9ea60d2edf5");
+    let public = public_key(&parameters, &private_key);
+    assert_eq!(public,
+               ProjKPoint::FinPoint(FP384::new(wide_hex("ec3a4e415b4e19a4568618029f427fa5da9a8bc4ae92e02e06aae5286b300c64def8f0ea9055866064a254515480bc13")),
+                                    FP384::new(wide_hex("8015d9b72d7d57244ea8ef9ac0c621896708a59367f9dfb9f54ca84b3f1c9db1288b231c3ae0d4fe7344fd2533264720"))));
+
+    let signature = Signature{
+        r: wide_hex("21b13d1e013c7fa1392d03c5f99af8b30c570c6f98d4ea8e354b63a21d3daa33bde1e888e63355d92fa2b3c36d8fb2cd"),
+        s: wide_hex("f3aa443fb107745bf4bd77cb3891674632068a10ca67e3d45db2266fa7d1feebefdc63eccd1ac42ec0cb8668a4fa0ab0"),
+    };
+    assert_eq!(sign(&parameters, &private_key, b"sample"), signature);
+    assert!(verify(&parameters, &public, b"sample", &signature));
+    assert!(!verify(&parameters, &public, b"test", &signature));
+}
+
+#[test]
+fn secp256k1_signatures(){
+    let parameters = secp256k1();
+    let private_key = hex(RFC6979_PRIVATE_KEY);
+    let public = public_key(&parameters, &private_key);
+    assert_eq!(public,
+               ProjKPoint::FinPoint(FSecp256k1::new(hex("2c8c31fc9f990c6b55e3865a184a4ce50e09481f2eaeb3e60ec1cea13a6ae645")),
+                                    FSecp256k1::new(hex("64b95e4fdb6948c0386e189b006a29f686769b011704275e4459822dc3328085"))));
+
+    let signature = Signature{
+        r: hex("432310e32cb80eb6503a26ce83cc165c783b870845fb8aad6d970889fcd7a6c8"),
+        s: hex("530128b6b81c548874a6305d93ed071ca6e05074d85863d4056ce89b02bfab69"),
+    };
+    assert_eq!(sign(&parameters, &private_key, b"sample"), signature);
+    assert!(verify(&parameters, &public, b"sample", &signature));
+    assert!(!verify(&parameters, &public, b"test", &signature));
+}
+
+#[test]
+fn sign_verify_recover(){
+    let parameters = p256();
+    for _i in 1..3{
+        let (private_key, public) = generate_keypair(&parameters);
+        let digest = Sha256::digest(b"message");
+        let (signature, recovery_id) = sign_digest(&parameters, &private_key, &digest);
+
+        assert!(verify_digest(&parameters, &public, &digest, &signature));
+        assert_eq!(recover_public_key(&parameters, &digest, &signature, recovery_id), Some(public.clone()));
+        assert!(recover_public_key(&parameters, &digest, &signature, recovery_id ^ 1) != Some(public.clone()));
+
+        let forged = Signature{
+            r: signature.r.clone(),
+            s: modulo(signature.s + Integer::from(1), &parameters.order),
+        };
+        assert!(!verify_digest(&parameters, &public, &digest, &forged));
+    }
+}
+
+// Toy parameters with a generator of small prime order, so that r or s is often zero
+fn small_order_parameters() -> DomainParameters<GL10169>{
+    loop{
+        let curve = EllipticCurve::new_reduced_weierstrass(GL10169::new(Integer::sample_uniform(&Integer::from(0), &Integer::from(10169))),
+                                                           GL10169::new(Integer::sample_uniform(&Integer::from(0), &Integer::from(10169))));
+        if curve.discriminant() == GL10169::from_int(0){
+            continue;
+        }
+        let point = curve.sample_point();
+        let order = curve.point_order(&point);
+        let small_prime = factor(&order).into_iter().map(|(l, _e)| l).find(|l| Integer::from(10) < *l && *l < Integer::from(50));
+        if let Some(l) = small_prime{
+            assert!(is_probable_prime(&l));
+            let generator = curve.scalar_mult(order/l.clone(), point);
+            return DomainParameters{ curve, generator, order: l };
+        }
+    }
+}
+
+#[test]
+fn signing_retries_invalid_nonces(){
+    let parameters = small_order_parameters();
+    let n = &parameters.order;
+    let mut retries = 0;
+    // the nonce only depends on the key and the digest modulo n, so every key is tried
+    let mut private_key = Integer::from(1);
+    while &private_key < n{
+        let public = public_key(&parameters, &private_key);
+        for i in 0u32..20{
+            let digest = Sha256::digest(i.to_be_bytes());
+            let k = deterministic_nonce(n, &private_key, &digest);
+            if let ProjKPoint::FinPoint(x, _) = parameters.curve.scalar_mult(k.clone(), parameters.generator.clone()){
+                let r = modulo(x.to_integer(), n);
+                let s = modulo(mod_inverse(k, n).unwrap()*(bits2int(&digest, n) + r.clone()*private_key.clone()), n);
+                if r == Integer::from(0) || s == Integer::from(0){
+                    retries += 1;
+                }
+            }
+
+            let (signature, _recovery_id) = sign_digest(&parameters, &private_key, &digest);
+            assert!(signature.r != Integer::from(0) && signature.s != Integer::from(0));
+            assert!(verify_digest(&parameters, &public, &digest, &signature));
+        }
+        private_key += Integer::from(1);
+    }
+    assert!(retries > 0);
+}
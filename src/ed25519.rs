@@ -0,0 +1,133 @@
+use std::convert::TryInto;
+
+use sha2::{Digest, Sha512};
+
+use crate::field::{Field, IntegerTrait};
+use crate::finite_fields::FiniteField;
+use crate::elliptic_curves::twisted_edwards::ExtendedPoint;
+use crate::integers::{modulo, from_le_bytes, to_le_bytes};
+use crate::standard_curves::{Integer, F25519, edwards25519};
+use crate::ecdh::decode_scalar_25519;
+
+// Prime order 2^252 + 27742317777372353535851937790883648493 of the base point
+pub fn group_order() -> Integer{
+    (Integer::from(1) << 252) + Integer::from_str_radix("27742317777372353535851937790883648493", 10).unwrap()
+}
+
+// The point (x, 4/5) with x even
+pub fn base_point() -> ExtendedPoint<F25519>{
+    let y = F25519::from_int(4)/F25519::from_int(5);
+    ExtendedPoint::from_affine(recover_x(&y, false).unwrap(), y)
+}
+
+// The abscissa of sign x_0 of the points of ordinate y, None if there is none
+fn recover_x(y : &F25519, x_0 : bool) -> Option<F25519>{
+    let ell = edwards25519();
+    let one = F25519::from_int(1);
+    // x^2 = (y^2 - 1)/(d*y^2 - a)
+    let x2 = (y.clone()*y.clone() - one.clone())/(ell.d*y.clone()*y.clone() - ell.a);
+    if x2 == F25519::from_int(0){
+        return if x_0 { None } else { Some(x2) };
+    }
+    if x2.legendre_symbol() == -1{
+        return None;
+    }
+    let x = x2.square_root();
+    if (x.to_integer()%2 == Integer::from(1)) == x_0{
+        Some(x)
+    }else{
+        Some(-x)
+    }
+}
+
+// Little-endian ordinate, with the parity of the abscissa in the most significant bit
+pub fn encode_point(point : &ExtendedPoint<F25519>) -> [u8; 32]{
+    let (x, y) = point.to_affine();
+    let mut bytes : [u8; 32] = to_le_bytes(&y.to_integer(), 32).try_into().unwrap();
+    if x.to_integer()%2 == Integer::from(1){
+        bytes[31] |= 0x80;
+    }
+    bytes
+}
+
+pub fn decode_point(bytes : &[u8; 32]) -> Option<ExtendedPoint<F25519>>{
+    let mut y = *bytes;
+    let x_0 = y[31] & 0x80 != 0;
+    y[31] &= 0x7f;
+    let y = from_le_bytes(&y);
+    if y >= F25519::cardinal(){
+        return None;
+    }
+    let y = F25519::new(y);
+    Some(ExtendedPoint::from_affine(recover_x(&y, x_0)?, y))
+}
+
+// Secret scalar and prefix derived from the SHA-512 hash of the secret key
+fn expand_secret_key(secret_key : &[u8; 32]) -> (Integer, Vec<u8>){
+    let h = Sha512::digest(secret_key);
+    (decode_scalar_25519(&h[..32].try_into().unwrap()), h[32..].to_vec())
+}
+
+// The 512-bit hash as h_0 + 2^256 h_1 modulo the group order, without integers wider than 256 bits
+fn hash_to_scalar(data : &[&[u8]]) -> Integer{
+    let mut hasher = Sha512::new();
+    for d in data{
+        hasher.update(d);
+    }
+    let h = hasher.finalize();
+    let n = group_order();
+    let (h_0, h_1) : (Integer, Integer) = (from_le_bytes(&h[..32]), from_le_bytes(&h[32..]));
+    let shift = modulo(Integer::from(1) << 256, &n);
+    modulo(h_0, &n).add_mod(&modulo(h_1, &n).mul_mod(&shift, &n), &n)
+}
+
+pub fn public_key(secret_key : &[u8; 32]) -> [u8; 32]{
+    let (s, _) = expand_secret_key(secret_key);
+    encode_point(&edwards25519().scalar_mult(s, base_point()))
+}
+
+pub fn generate_keypair() -> ([u8; 32], [u8; 32]){
+    let secret_key : [u8; 32] = rand::random();
+    (secret_key, public_key(&secret_key))
+}
+
+pub fn sign(secret_key : &[u8; 32], message : &[u8]) -> [u8; 64]{
+    let ell = edwards25519();
+    let n = group_order();
+    let (s, prefix) = expand_secret_key(secret_key);
+    let public_key = encode_point(&ell.scalar_mult(s.clone(), base_point()));
+
+    let r = hash_to_scalar(&[&prefix, message]);
+    let big_r = encode_point(&ell.scalar_mult(r.clone(), base_point()));
+    let k = hash_to_scalar(&[&big_r, &public_key, message]);
+
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(&big_r);
+    signature[32..].copy_from_slice(&to_le_bytes(&r.add_mod(&k.mul_mod(&modulo(s, &n), &n), &n), 32));
+    signature
+}
+
+// Cofactored verification: [8][S]B = [8]R + [8][k]A
+pub fn verify(public_key : &[u8; 32], message : &[u8], signature : &[u8; 64]) -> bool{
+    let ell = edwards25519();
+    let big_a = match decode_point(public_key){
+        Some(point) => point,
+        None => return false,
+    };
+    let big_r = match decode_point(&signature[..32].try_into().unwrap()){
+        Some(point) => point,
+        None => return false,
+    };
+    let s : Integer = from_le_bytes(&signature[32..]);
+    if s >= group_order(){
+        return false;
+    }
+    let k = hash_to_scalar(&[&signature[..32], public_key, message]);
+
+    let lhs = ell.scalar_mult(Integer::from(8)*s, base_point());
+    let rhs = ell.add_points(ell.scalar_mult(Integer::from(8), big_r), ell.scalar_mult(Integer::from(8)*k, big_a));
+    lhs == rhs
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,77 @@
+use super::*;
+
+use crate::elliptic_curves::ProjKPoint;
+use crate::field::IntegerTrait;
+use crate::standard_curves::curve25519;
+
+fn hex_bytes(s : &str) -> Vec<u8>{
+    (0..s.len()/2).map(|i| u8::from_str_radix(&s[2*i..2*i+2], 16).unwrap()).collect()
+}
+
+// (secret key, public key, message, signature) of RFC 8032, section 7.1
+const TEST_VECTORS : [(&str, &str, &str, &str); 3] = [
+    ("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+     "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+     "",
+     "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"),
+    ("4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+     "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+     "72",
+     "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00"),
+    ("c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+     "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+     "af82",
+     "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a"),
+];
+
+#[test]
+fn rfc8032_test_vectors(){
+    for (secret_key, public, message, signature) in TEST_VECTORS.iter(){
+        let secret_key : [u8; 32] = hex_bytes(secret_key).try_into().unwrap();
+        let public : [u8; 32] = hex_bytes(public).try_into().unwrap();
+        let signature : [u8; 64] = hex_bytes(signature).try_into().unwrap();
+        let message = hex_bytes(message);
+
+        assert_eq!(public_key(&secret_key), public);
+        assert_eq!(sign(&secret_key, &message), signature);
+        assert!(verify(&public, &message, &signature));
+    }
+}
+
+#[test]
+fn edwards25519_is_birational_to_curve25519(){
+    let ell = edwards25519();
+    assert_eq!(ell.to_montgomery(), curve25519());
+    match ell.to_montgomery_point(&base_point()){
+        ProjKPoint::FinPoint(u, _) => assert_eq!(u, F25519::from_int(9)),
+        ProjKPoint::InfPoint => panic!("the base point is not the neutral point"),
+    }
+    assert_eq!(ell.scalar_mult(group_order(), base_point()), ExtendedPoint::neutral_point());
+}
+
+#[test]
+fn point_encoding(){
+    let ell = edwards25519();
+    for _i in 0..10{
+        let point = ell.scalar_mult(Integer::sample_uniform(&Integer::from(0), &group_order()), base_point());
+        assert_eq!(decode_point(&encode_point(&point)), Some(point));
+    }
+    let mut bytes = [0xffu8; 32]; // ordinate above p
+    assert_eq!(decode_point(&bytes), None);
+    bytes = [0; 32];
+    bytes[0] = 1;
+    bytes[31] = 0x80; // (-0, 1)
+    assert_eq!(decode_point(&bytes), None);
+}
+
+#[test]
+fn sign_and_verify(){
+    let (secret_key, public) = generate_keypair();
+    let message = b"message signed entirely inside the crate";
+    let mut signature = sign(&secret_key, message);
+    assert!(verify(&public, message, &signature));
+    assert!(!verify(&public, b"another message", &signature));
+
+    signature[40] ^= 1;
+    assert!(!verify(&public, message, &signature));
+}
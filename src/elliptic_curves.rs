@@ -3,10 +3,19 @@ use std::fmt;
 use std::fmt::Display;
 
 use crate::field::{Field};
+use crate::error::Error;
 
 pub mod fp_elliptic_curves;
-
-#[derive(Debug, PartialEq, Clone, Copy)]
+pub mod pairing;
+pub mod discrete_log;
+pub mod twisted_edwards;
+pub mod projective;
+pub mod complete_addition;
+pub mod windowed;
+pub mod multi_scalar;
+pub mod glv;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum ProjKPoint<K> {
     FinPoint(K, K),
     InfPoint,
@@ -68,11 +77,33 @@ impl<K> EllipticCurve<K>
             EllipticCurve::new_reduced_weierstrass(-K::from_int(27)*self.c4(), -K::from_int(54)*self.c6())
         }
 
+        // Send p to to_reduced_weierstrass through (x, y) -> (36x + 3b2, 108(2y + a_1x + a_3))
+        pub fn to_reduced_weierstrass_point(&self, p : ProjKPoint<K>) -> ProjKPoint<K>{
+            match p{
+                ProjKPoint::InfPoint => ProjKPoint::InfPoint,
+                ProjKPoint::FinPoint(x, y) => {
+                    let y = K::from_int(108)*(K::from_int(2)*y + self.a_1.clone()*x.clone() + self.a_3.clone());
+                    ProjKPoint::FinPoint(K::from_int(36)*x + K::from_int(3)*self.b2(), y)
+                }
+            }
+        }
+
         // Return the curve quotiented by the subgroup generated by p
         pub fn velu_curve(&self, p : &ProjKPoint<K>) -> EllipticCurve<K>{
             if !self.is_reduced_weierstrass(){
                 panic!("VELU formula must be used only with curves in reduced form");
             }
+            self.try_velu_curve(p).unwrap_or_else(|error| panic!("{}", error))
+        }
+
+        // velu_curve returning an error instead of panicking, also when the base ring is not a field
+        pub fn try_velu_curve(&self, p : &ProjKPoint<K>) -> Result<EllipticCurve<K>, Error>{
+            if !self.is_reduced_weierstrass(){
+                return Err(Error::NotReducedWeierstrass);
+            }
+            if !self.is_on_curve(p){
+                return Err(Error::NotOnCurve);
+            }
             let mut g = p.clone();
             let a = self.a_4.clone();
             let b = self.a_6.clone();
@@ -81,9 +112,9 @@ impl<K> EllipticCurve<K>
             while let ProjKPoint::FinPoint(x_g, _) = &g {
                 sum_a += K::from_int(3)*x_g.clone()*x_g.clone() + a.clone();
                 sum_b += K::from_int(5)*x_g.clone()*x_g.clone()*x_g.clone() + K::from_int(3)*a.clone()*x_g.clone() + K::from_int(2)*b.clone();
-                g = self.add_points(g, p.clone());
+                g = self.add_points_on_curve(g, p.clone())?;
             }
-            EllipticCurve::new_reduced_weierstrass(a - K::from_int(5)*sum_a, b - K::from_int(7)*sum_b)
+            Ok(EllipticCurve::new_reduced_weierstrass(a - K::from_int(5)*sum_a, b - K::from_int(7)*sum_b))
         }
 
         // Send the point q to the VELU curve of p
@@ -133,6 +164,12 @@ impl<K> EllipticCurve<K>
             self.a_3.clone()*self.a_3.clone() + K::from_int(4)*self.a_6.clone()
         }
 
+        fn b8(&self) -> K{
+            self.a_1.clone()*self.a_1.clone()*self.a_6.clone() + K::from_int(4)*self.a_2.clone()*self.a_6.clone()
+            - self.a_1.clone()*self.a_3.clone()*self.a_4.clone() + self.a_2.clone()*self.a_3.clone()*self.a_3.clone()
+            - self.a_4.clone()*self.a_4.clone()
+        }
+
         fn c4(&self) -> K{
             let b2 = self.b2();
             let b4 = self.b4();
@@ -157,10 +194,10 @@ impl<K> EllipticCurve<K>
             let b2 = self.b2();
             let b4 = self.b4();
             let b6 = self.b6();
+            let b8 = self.b8();
 
-            K::from_int(-4)*(b2.clone()*b2.clone()*b2.clone()*b6.clone() - b2.clone()*b2.clone()*b4.clone()*b4.clone() - 
-            K::from_int(36)*b2*b4.clone()*b6.clone() + K::from_int(32)*b4.clone()*b4.clone()*b4 + K::from_int(108)*b6.clone()*b6)
-            //-b2*b2*b8 - K::from_int(8)*b4*b4*b4 - K::from_int(27)*b6*b6 + K::from_int(9)*b2*b4*b6
+            -b2.clone()*b2.clone()*b8 - K::from_int(8)*b4.clone()*b4.clone()*b4.clone() - K::from_int(27)*b6.clone()*b6.clone()
+            + K::from_int(9)*b2*b4*b6
         }
 
         pub fn is_on_curve(&self, point : &ProjKPoint<K>) -> bool{
@@ -184,38 +221,64 @@ impl<K> EllipticCurve<K>
             }
         }
 
+        // Coefficients (lambda, nu) of the line y = lambda*x + nu through two finite points which are not opposite,
+        // the tangent line when they are equal
+        fn line_coefficients(&self, x1 : K, y1 : K, x2 : K, y2 : K) -> (K, K){
+            self.try_line_coefficients(x1, y1, x2, y2).expect("the denominator of the line is not invertible")
+        }
+
+        // The denominators can only fail to be invertible when the base ring is not a field
+        fn try_line_coefficients(&self, x1 : K, y1 : K, x2 : K, y2 : K) -> Result<(K, K), Error>{
+            let a_1 = self.a_1.clone();
+            let a_2 = self.a_2.clone();
+            let a_3 = self.a_3.clone();
+            let a_4 = self.a_4.clone();
+            let a_6 = self.a_6.clone();
+
+            if x1 != x2 {
+                let denominator = (x2.clone()-x1.clone()).try_inv()?;
+                Ok(((y2.clone()-y1.clone())*denominator.clone(), (y1*x2.clone() - y2*x1.clone())*denominator))
+            }else{
+                let denominator = (K::from_int(2)*y1.clone() + a_1.clone()*x1.clone() + a_3.clone()).try_inv()?;
+                Ok(((K::from_int(3)*x1.clone()*x1.clone() + K::from_int(2)*a_2*x1.clone() + a_4.clone() - a_1*y1.clone())*denominator.clone(),
+
+                 (-x1.clone()*x1.clone()*x1.clone() + a_4*x1.clone() + K::from_int(2)*a_6 - a_3*y1)*denominator))
+            }
+        }
+
         pub fn add_points(&self, point1 : ProjKPoint<K>, point2 : ProjKPoint<K>) -> ProjKPoint<K>{
             assert!(self.is_on_curve(&point1));
             assert!(self.is_on_curve(&point2));
+            self.add_points_on_curve(point1, point2).expect("the denominator of the line is not invertible")
+        }
+
+        // add_points returning an error instead of panicking, for instance when a denominator is a non unit
+        // of Z/nZ, which reveals a factor of n in ECM
+        pub fn checked_add_points(&self, point1 : ProjKPoint<K>, point2 : ProjKPoint<K>) -> Result<ProjKPoint<K>, Error>{
+            if !self.is_on_curve(&point1) || !self.is_on_curve(&point2){
+                return Err(Error::NotOnCurve);
+            }
+            self.add_points_on_curve(point1, point2)
+        }
 
+        fn add_points_on_curve(&self, point1 : ProjKPoint<K>, point2 : ProjKPoint<K>) -> Result<ProjKPoint<K>, Error>{
             use ProjKPoint::*;
 
             let a_1 = self.a_1.clone();
             let a_2 = self.a_2.clone();
             let a_3 = self.a_3.clone();
-            let a_4 = self.a_4.clone();
-            let a_6 = self.a_6.clone();
 
             if point1 == self.neg_point(point2.clone()){
-                return InfPoint;
+                return Ok(InfPoint);
             }
             match (point1.clone(), point2.clone()){
-                (InfPoint, _) => point2,
-                (_, InfPoint) => point1,
+                (InfPoint, _) => Ok(point2),
+                (_, InfPoint) => Ok(point1),
                 (FinPoint(x1, y1), FinPoint(x2, y2)) => {
-                    let (lambda, nu) = 
-                        if x1 != x2 {
-                            ((y2.clone()-y1.clone())/(x2.clone()-x1.clone()), (y1*x2.clone() - y2*x1.clone())/(x2.clone()-x1.clone()))
-                        }else{
-                            ((K::from_int(3)*x1.clone()*x1.clone() + K::from_int(2)*a_2.clone()*x1.clone() + a_4.clone() - a_1.clone()*y1.clone())/
-                             (K::from_int(2)*y1.clone() + a_1.clone()*x1.clone() + a_3.clone()), 
-
-                             (-x1.clone()*x1.clone()*x1.clone() + a_4*x1.clone() + K::from_int(2)*a_6 - a_3.clone()*y1.clone())/
-                             (K::from_int(2)*y1 + a_1.clone()*x1.clone() + a_3.clone()))
-                        };
+                    let (lambda, nu) = self.try_line_coefficients(x1.clone(), y1, x2.clone(), y2)?;
                     let x3 = lambda.clone()*lambda.clone() +a_1.clone()*lambda.clone() - a_2 - x1 - x2;
                     let y3 = -(lambda + a_1)*x3.clone() - nu - a_3;
-                    FinPoint(x3, y3)
+                    Ok(FinPoint(x3, y3))
                 }
             }
         }
@@ -258,5 +321,5 @@ impl<K> EllipticCurve<K>
         }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "gmp"))]
 mod test;
\ No newline at end of file
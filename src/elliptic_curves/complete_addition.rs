@@ -0,0 +1,123 @@
+use super::*;
+use super::projective::HomogeneousPoint;
+use crate::integers::{modulo, bit_length};
+
+// Complete addition law of Renes, Costello and Batina (2015) for y^2 = x^3 + a*x + b in homogeneous projective
+// coordinates: it is valid for every pair of points, including equal, opposite and infinite points,
+// as soon as the curve has odd order
+impl<K> EllipticCurve<K>
+    where K : Field{
+
+        // Algorithm 1 of the paper, with b3 = 3*b
+        fn complete_add_generic(&self, p : HomogeneousPoint<K>, q : HomogeneousPoint<K>) -> HomogeneousPoint<K>{
+            let a = self.a_4.clone();
+            let b3 = K::from_int(3)*self.a_6.clone();
+
+            let mut t0 = p.x.clone()*q.x.clone();
+            let mut t1 = p.y.clone()*q.y.clone();
+            let mut t2 = p.z.clone()*q.z.clone();
+            let mut t3 = (p.x.clone() + p.y.clone())*(q.x.clone() + q.y.clone());
+            let mut t4 = t0.clone() + t1.clone();
+            t3 -= t4;
+            t4 = (p.x.clone() + p.z.clone())*(q.x.clone() + q.z.clone());
+            let mut t5 = t0.clone() + t2.clone();
+            t4 -= t5;
+            t5 = (p.y + p.z)*(q.y + q.z);
+            let mut x3 = t1.clone() + t2.clone();
+            t5 -= x3;
+            let mut z3 = a.clone()*t4.clone();
+            x3 = b3.clone()*t2.clone();
+            z3 += x3;
+            x3 = t1.clone() - z3.clone();
+            z3 += t1;
+            let mut y3 = x3.clone()*z3.clone();
+            t1 = t0.clone() + t0.clone() + t0.clone();
+            t2 = a.clone()*t2;
+            t4 = b3*t4;
+            t1 += t2.clone();
+            t2 = a*(t0 - t2);
+            t4 += t2;
+            t0 = t1.clone()*t4.clone();
+            y3 += t0;
+            t0 = t5.clone()*t4;
+            x3 = t3.clone()*x3 - t0;
+            t0 = t3*t1;
+            z3 = t5*z3 + t0;
+            HomogeneousPoint{ x: x3, y: y3, z: z3 }
+        }
+
+        // Algorithm 4 of the paper, when a = -3
+        fn complete_add_minus_three(&self, p : HomogeneousPoint<K>, q : HomogeneousPoint<K>) -> HomogeneousPoint<K>{
+            let b = self.a_6.clone();
+
+            let mut t0 = p.x.clone()*q.x.clone();
+            let mut t1 = p.y.clone()*q.y.clone();
+            let mut t2 = p.z.clone()*q.z.clone();
+            let t3 = (p.x.clone() + p.y.clone())*(q.x.clone() + q.y.clone()) - t0.clone() - t1.clone();
+            let t4 = (p.y + p.z.clone())*(q.y + q.z.clone()) - t1.clone() - t2.clone();
+            let mut y3 = (p.x + p.z)*(q.x + q.z) - t0.clone() - t2.clone();
+            let mut z3 = b.clone()*t2.clone();
+            let mut x3 = y3.clone() - z3;
+            x3 = x3.clone() + x3.clone() + x3;
+            z3 = t1.clone() - x3.clone();
+            x3 += t1;
+            y3 = b*y3;
+            t2 = t2.clone() + t2.clone() + t2;
+            y3 = y3 - t2.clone() - t0.clone();
+            y3 = y3.clone() + y3.clone() + y3;
+            t0 = t0.clone() + t0.clone() + t0 - t2;
+            t1 = t4.clone()*y3.clone();
+            t2 = t0.clone()*y3;
+            y3 = x3.clone()*z3.clone() + t2;
+            x3 = t3.clone()*x3 - t1;
+            z3 = t4*z3 + t3*t0;
+            HomogeneousPoint{ x: x3, y: y3, z: z3 }
+        }
+
+        // Sum of p and q without any case distinction on the points
+        pub fn complete_add(&self, p : HomogeneousPoint<K>, q : HomogeneousPoint<K>) -> HomogeneousPoint<K>{
+            if !self.is_reduced_weierstrass(){
+                panic!("complete addition must be used only with curves in reduced form");
+            }
+            if self.a_4 == K::from_int(-3){
+                self.complete_add_minus_three(p, q)
+            }else{
+                self.complete_add_generic(p, q)
+            }
+        }
+
+        // Exchange p and q when bit is 1 and leave them when it is 0, with the same field operations in both cases
+        fn conditional_swap(bit : &K, p : &mut HomogeneousPoint<K>, q : &mut HomogeneousPoint<K>){
+            let dx = bit.clone()*(q.x.clone() - p.x.clone());
+            let dy = bit.clone()*(q.y.clone() - p.y.clone());
+            let dz = bit.clone()*(q.z.clone() - p.z.clone());
+            p.x += dx.clone();
+            p.y += dy.clone();
+            p.z += dz.clone();
+            q.x -= dx;
+            q.y -= dy;
+            q.z -= dz;
+        }
+
+        // Montgomery ladder where both the additions and the doublings use the complete addition law, for a point
+        // whose order divides order. The scalar is reduced modulo order and the ladder always runs bit_length(order)
+        // steps, choosing its operands by conditional swaps instead of branches on the bits
+        pub fn scalar_mult_complete(&self, n : K::Integer, point : ProjKPoint<K>, order : &K::Integer) -> HomogeneousPoint<K>{
+            assert!(self.is_on_curve(&point));
+            let n = modulo(n, order);
+
+            let mut r0 = HomogeneousPoint::infinite_point();
+            let mut r1 = HomogeneousPoint::from_affine(point);
+            for i in (0..bit_length(order)).rev(){
+                let bit = K::new((n.clone() >> i) & K::Integer::from(1));
+                Self::conditional_swap(&bit, &mut r0, &mut r1);
+                r1 = self.complete_add(r0.clone(), r1);
+                r0 = self.complete_add(r0.clone(), r0);
+                Self::conditional_swap(&bit, &mut r0, &mut r1);
+            }
+            r0
+        }
+}
+
+#[cfg(all(test, feature = "gmp"))]
+mod test;
@@ -0,0 +1,80 @@
+use super::*;
+
+use crate::field::*;
+use crate::finite_fields::*;
+
+pub type Integer = gmp::mpz::Mpz;
+
+const P : u32 = 10169;
+
+declare_finite_field!(K, Integer, Integer::from(P), m10169);
+
+fn sample_element() -> K{
+    K::new(Integer::sample_uniform(&Integer::from(0), &Integer::from(P)))
+}
+
+// Reduced Weierstrass curve of odd order, i.e. without point of order 2, with a = -3 when minus_three is set
+fn sample_odd_order_curve(minus_three : bool) -> EllipticCurve<K>{
+    loop{
+        let a = if minus_three { K::from_int(-3) } else { sample_element() };
+        let b = sample_element();
+        let ell = EllipticCurve::new_reduced_weierstrass(a.clone(), b.clone());
+        let has_two_torsion = (0..P).any(|x| {
+            let x = K::new(Integer::from(x));
+            x.clone()*x.clone()*x.clone() + a.clone()*x + b.clone() == K::from_int(0)
+        });
+        if ell.discriminant() != K::from_int(0) && !has_two_torsion{
+            return ell;
+        }
+    }
+}
+
+#[test]
+fn complete_add_coincide_with_affine(){
+    for j in 0..10{
+        let ell = sample_odd_order_curve(j%2 == 0);
+        for i in 0..50{
+            let p = ell.sample_point();
+            let q = match i%4{
+                0 => p.clone(),
+                1 => ell.neg_point(p.clone()),
+                2 => ProjKPoint::InfPoint,
+                _ => ell.sample_point(),
+            };
+            let (p_hom, q_hom) = (HomogeneousPoint::from_affine(p.clone()), HomogeneousPoint::from_affine(q.clone()));
+            let sum = ell.add_points(p.clone(), q.clone());
+            assert_eq!(ell.complete_add(p_hom.clone(), q_hom.clone()).to_affine(), sum);
+            assert_eq!(ell.complete_add(q_hom.clone(), p_hom).to_affine(), sum);
+            assert_eq!(ell.complete_add(q_hom.clone(), q_hom).to_affine(), ell.add_points(q.clone(), q));
+        }
+        assert!(ell.complete_add(HomogeneousPoint::infinite_point(), HomogeneousPoint::infinite_point()).is_infinite());
+    }
+}
+
+#[test]
+fn scalar_mult_complete_coincide(){
+    for j in 0..10{
+        let ell = sample_odd_order_curve(j%2 == 0);
+        for _i in 0..20{
+            let p = ell.sample_point();
+            let order = ell.point_order(&p);
+            let n = Integer::sample_uniform(&Integer::from(-2*P as i64), &Integer::from(2*P));
+            assert_eq!(ell.scalar_mult_complete(n.clone(), p.clone(), &order).to_affine(), ell.scalar_mult(n, p.clone()));
+            assert!(ell.scalar_mult_complete(order.clone(), p.clone(), &order).is_infinite());
+            assert_eq!(ell.scalar_mult_complete(Integer::from(1), p.clone(), &order).to_affine(), p);
+        }
+    }
+}
+
+#[test]
+fn complete_add_on_p256(){
+    let parameters = crate::standard_curves::p256();
+    let (ell, g) = (&parameters.curve, parameters.generator.clone());
+    let g_hom = HomogeneousPoint::from_affine(g.clone());
+    let g2 = ell.complete_add(g_hom.clone(), g_hom.clone());
+    assert_eq!(g2.to_affine(), ell.add_points(g.clone(), g.clone()));
+    assert_eq!(ell.complete_add(g2, g_hom).to_affine(), ell.scalar_mult(gmp::mpz::Mpz::from(3), g.clone()));
+    assert!(ell.scalar_mult_complete(parameters.order.clone(), g.clone(), &parameters.order).is_infinite());
+    let n = Integer::from(0x1234_5678_9abc_def0u64) << 150;
+    assert_eq!(ell.scalar_mult_complete(n.clone(), g.clone(), &parameters.order).to_affine(), ell.scalar_mult(n, g));
+}
@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+
+use crate::finite_fields::FiniteField;
+use crate::field::IntegerTrait;
+use crate::integers::{modulo, mod_inverse, isqrt, factor, crt};
+
+use super::*;
+
+pub mod parallel_rho;
+
+const RHO_PARTITIONS : u64 = 20;
+const RHO_ATTEMPTS : u32 = 10;
+const KANGAROO_JUMPS : u64 = 16;
+const KANGAROO_ATTEMPTS : u32 = 10;
+const BSGS_BOUND : u64 = 1 << 40; // prime order subproblems above this bound are solved by rho
+
+// A point of a random walk, together with (a, b) such that point = [a]p + [b]q
+#[derive(Clone)]
+struct WalkPoint<K : FiniteField>{
+    point : ProjKPoint<K>,
+    a : K::Integer,
+    b : K::Integer,
+}
+
+impl<K> EllipticCurve<K>
+    where K : FiniteField + fmt::Display{
+
+        fn linear_combination(&self, a : K::Integer, p : &ProjKPoint<K>, b : K::Integer, q : &ProjKPoint<K>) -> ProjKPoint<K>{
+            self.add_points(self.scalar_mult(a, p.clone()), self.scalar_mult(b, q.clone()))
+        }
+
+        fn random_walk_point(&self, p : &ProjKPoint<K>, q : &ProjKPoint<K>, n : &K::Integer) -> WalkPoint<K>{
+            let a = K::Integer::sample_uniform(&K::Integer::from(0), n);
+            let b = K::Integer::sample_uniform(&K::Integer::from(0), n);
+            WalkPoint{
+                point: self.linear_combination(a.clone(), p, b.clone(), q),
+                a,
+                b,
+            }
+        }
+
+        // Index of the partition of the r-adding walk containing point
+        fn partition(point : &ProjKPoint<K>, r : u64) -> usize{
+            match point{
+                ProjKPoint::InfPoint => 0,
+                ProjKPoint::FinPoint(x, _) => (x.to_integer()%r).to_u64().unwrap() as usize,
+            }
+        }
+
+        fn rho_step(&self, x : WalkPoint<K>, steps : &[WalkPoint<K>], n : &K::Integer) -> WalkPoint<K>{
+            let step = &steps[Self::partition(&x.point, steps.len() as u64)];
+            WalkPoint{
+                point: self.add_points(x.point, step.point.clone()),
+                a: modulo(x.a + step.a.clone(), n),
+                b: modulo(x.b + step.b.clone(), n),
+            }
+        }
+
+        // Solve a_1 + b_1*m = a_2 + b_2*m mod n, and check the solution
+        fn solve_collision(&self, x1 : &WalkPoint<K>, x2 : &WalkPoint<K>, p : &ProjKPoint<K>, q : &ProjKPoint<K>, n : &K::Integer)
+            -> Option<K::Integer>{
+            let b_inv = mod_inverse(x2.b.clone() - x1.b.clone(), n)?;
+            let m = modulo((x1.a.clone() - x2.a.clone())*b_inv, n);
+            if &self.scalar_mult(m.clone(), p.clone()) == q{
+                Some(m)
+            }else{
+                None
+            }
+        }
+
+        // Pollard rho with an r-adding walk and Floyd cycle detection: return m such that q = [m]p, where p has prime order n
+        pub fn ecdlp_rho(&self, p : &ProjKPoint<K>, q : &ProjKPoint<K>, n : K::Integer) -> Option<K::Integer>{
+            for _attempt in 0..RHO_ATTEMPTS{
+                let steps : Vec<WalkPoint<K>> = (0..RHO_PARTITIONS).map(|_| self.random_walk_point(p, q, &n)).collect();
+
+                let start = self.random_walk_point(p, q, &n);
+                let mut tortoise = self.rho_step(start.clone(), &steps, &n);
+                let mut hare = self.rho_step(tortoise.clone(), &steps, &n);
+                while tortoise.point != hare.point{
+                    tortoise = self.rho_step(tortoise, &steps, &n);
+                    hare = self.rho_step(self.rho_step(hare, &steps, &n), &steps, &n);
+                }
+
+                if let Some(m) = self.solve_collision(&tortoise, &hare, p, q, &n){
+                    return Some(m);
+                }
+            }
+            None
+        }
+
+        // Pollard kangaroo (lambda) method: return m in [a, b] such that q = [m]p, if any
+        pub fn ecdlp_kangaroo(&self, p : &ProjKPoint<K>, q : &ProjKPoint<K>, a : K::Integer, b : K::Integer) -> Option<K::Integer>{
+            let sqrt_width = isqrt(&(b.clone() - a.clone())) + K::Integer::from(1);
+
+            for _attempt in 0..KANGAROO_ATTEMPTS{
+                // jumps of mean size sqrt(b - a)/2
+                let jumps : Vec<(K::Integer, ProjKPoint<K>)> = (0..KANGAROO_JUMPS).map(|_| {
+                    let s = K::Integer::sample_uniform(&K::Integer::from(1), &(sqrt_width.clone() + K::Integer::from(1)));
+                    (s.clone(), self.scalar_mult(s, p.clone()))
+                }).collect();
+                let jump = |x : ProjKPoint<K>, distance : K::Integer| {
+                    let (s, s_p) = &jumps[Self::partition(&x, KANGAROO_JUMPS)];
+                    (self.add_points(x, s_p.clone()), distance + s.clone())
+                };
+
+                // the tame kangaroo starts at [b]p and sets a trap after sqrt(b - a) jumps
+                let mut tame = self.scalar_mult(b.clone(), p.clone());
+                let mut tame_distance = K::Integer::from(0);
+                let mut i = K::Integer::from(0);
+                while i < sqrt_width{
+                    let (x, d) = jump(tame, tame_distance);
+                    tame = x;
+                    tame_distance = d;
+                    i += K::Integer::from(1);
+                }
+
+                // the wild kangaroo starts at q and stops once it has passed the trap
+                let mut wild = q.clone();
+                let mut wild_distance = K::Integer::from(0);
+                let max_distance = b.clone() - a.clone() + tame_distance.clone();
+                while wild != tame && wild_distance <= max_distance{
+                    let (x, d) = jump(wild, wild_distance);
+                    wild = x;
+                    wild_distance = d;
+                }
+
+                if wild == tame{
+                    let m = b.clone() + tame_distance - wild_distance;
+                    if &self.scalar_mult(m.clone(), p.clone()) == q && a <= m && m <= b{
+                        return Some(m);
+                    }
+                }
+            }
+            None
+        }
+
+        // A multiple of the order of p, found by baby-step giant-step in the Hasse interval [q + 1 - 2 sqrt(q), q + 1 + 2 sqrt(q)]
+        // which contains the cardinal of the curve, with O(q^(1/4)) additions
+        fn order_multiple(&self, p : &ProjKPoint<K>) -> K::Integer{
+            assert!(self.is_on_curve(p), "point_order must be used with a point of the curve");
+            let q = K::cardinal();
+            let bound = K::Integer::from(2)*isqrt(&q) + K::Integer::from(2);
+            let low = if q.clone() + K::Integer::from(1) > bound{
+                q.clone() + K::Integer::from(1) - bound.clone()
+            }else{
+                K::Integer::from(1)
+            };
+            let width = K::Integer::from(2)*bound; // the interval is [low, low + width]
+            let m = isqrt(&width) + K::Integer::from(1);
+
+            let mut baby_steps = HashMap::new();
+            let mut baby_step = ProjKPoint::InfPoint;
+            let mut j = K::Integer::from(0);
+            while j < m{
+                baby_steps.entry(baby_step.clone()).or_insert_with(|| j.clone());
+                baby_step = self.add_points(baby_step, p.clone());
+                j += K::Integer::from(1);
+            }
+
+            // [low + i*m]p + [j]p = O
+            let mut giant = self.scalar_mult(low.clone(), p.clone());
+            let mut i = K::Integer::from(0);
+            while i <= m{
+                if let Some(j) = baby_steps.get(&self.neg_point(giant.clone())){
+                    return low + i*m.clone() + j.clone();
+                }
+                giant = self.add_points(giant, baby_step.clone());
+                i += K::Integer::from(1);
+            }
+            unreachable!("the cardinal of the curve is in the Hasse interval")
+        }
+
+        pub fn point_order(&self, p : &ProjKPoint<K>) -> K::Integer{
+            self.point_order_dividing(p, self.order_multiple(p))
+        }
+
+        // Order of p from a multiple n of it, for instance the cardinal of the curve when it is known
+        pub fn point_order_dividing(&self, p : &ProjKPoint<K>, n : K::Integer) -> K::Integer{
+            assert!(n > K::Integer::from(0), "the multiple of the order of p must be positive");
+            assert!(self.scalar_mult(n.clone(), p.clone()) == ProjKPoint::InfPoint, "the order of p does not divide n");
+            let mut order = n;
+            for (l, e) in factor(&order){
+                for _i in 0..e{
+                    if self.scalar_mult(order.clone()/l.clone(), p.clone()) != ProjKPoint::InfPoint{
+                        break;
+                    }
+                    order /= l.clone();
+                }
+            }
+            order
+        }
+
+        // Baby-step giant-step: return m in [0, n) such that q = [m]p, where n is at least the order of p
+        pub fn ecdlp_bsgs(&self, p : &ProjKPoint<K>, q : &ProjKPoint<K>, n : K::Integer) -> Option<K::Integer>{
+            let m = isqrt(&n) + K::Integer::from(1);
+
+            let mut baby_steps = HashMap::new();
+            let mut baby_step = ProjKPoint::InfPoint;
+            let mut j = K::Integer::from(0);
+            while j < m{
+                baby_steps.entry(baby_step.clone()).or_insert_with(|| j.clone()); // keep the smallest j
+                baby_step = self.add_points(baby_step, p.clone());
+                j += K::Integer::from(1);
+            }
+
+            let giant_step = self.neg_point(baby_step); // -[m]p
+            let mut gamma = q.clone();
+            let mut i = K::Integer::from(0);
+            while i < m{
+                if let Some(j) = baby_steps.get(&gamma){
+                    return Some(i*m.clone() + j.clone());
+                }
+                gamma = self.add_points(gamma, giant_step.clone());
+                i += K::Integer::from(1);
+            }
+            None
+        }
+
+        // Discrete logarithm in a subgroup of prime order l
+        fn ecdlp_prime_order(&self, p : &ProjKPoint<K>, q : &ProjKPoint<K>, l : K::Integer) -> Option<K::Integer>{
+            if l < K::Integer::from(BSGS_BOUND){
+                self.ecdlp_bsgs(p, q, l)
+            }else{
+                self.ecdlp_rho(p, q, l)
+            }
+        }
+
+        // Pohlig-Hellman: return m such that q = [m]p, computing the order of p and solving the problem modulo
+        // each prime power dividing it. None if q is not in the subgroup generated by p
+        pub fn discrete_log(&self, p : &ProjKPoint<K>, q : &ProjKPoint<K>) -> Option<K::Integer>{
+            self.discrete_log_dividing(p, q, self.order_multiple(p))
+        }
+
+        // discrete_log from a known multiple n of the order of p, such as the cardinal of the curve
+        pub fn discrete_log_dividing(&self, p : &ProjKPoint<K>, q : &ProjKPoint<K>, n : K::Integer) -> Option<K::Integer>{
+            let order = self.point_order_dividing(p, n);
+            let mut congruences = Vec::new();
+
+            for (l, e) in factor(&order){
+                let mut l_e = K::Integer::from(1);
+                for _i in 0..e{
+                    l_e *= l.clone();
+                }
+                let cofactor = order.clone()/l_e.clone();
+                let p_l = self.scalar_mult(cofactor.clone(), p.clone()); // of order l^e
+                let q_l = self.scalar_mult(cofactor, q.clone());
+                let gamma = self.scalar_mult(l_e.clone()/l.clone(), p_l.clone()); // of order l
+
+                // m = x_0 + x_1 l + ... + x_{e-1} l^{e-1} mod l^e
+                let mut m = K::Integer::from(0);
+                let mut l_j = K::Integer::from(1);
+                for _j in 0..e{
+                    let remainder = self.add_points(q_l.clone(), self.neg_point(self.scalar_mult(m.clone(), p_l.clone())));
+                    let q_j = self.scalar_mult(l_e.clone()/(l_j.clone()*l.clone()), remainder);
+                    let x_j = self.ecdlp_prime_order(&gamma, &q_j, l.clone())?;
+                    m += x_j*l_j.clone();
+                    l_j *= l.clone();
+                }
+                congruences.push((m, l_e));
+            }
+
+            let m = crt(&congruences);
+            if &self.scalar_mult(m.clone(), p.clone()) == q{
+                Some(m)
+            }else{
+                None
+            }
+        }
+}
+
+#[cfg(all(test, feature = "gmp"))]
+mod test;
@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use super::*;
+
+const PARALLEL_RHO_PARTITIONS : u64 = 32;
+const LOOK_AHEAD : usize = 4;
+const MAX_DISTINGUISHED_BITS : u32 = 59; // the walks are abandoned after 20 << bits steps, which must fit in a u64
+
+pub struct ParallelRhoParameters{
+    pub threads : usize,
+    pub distinguished_bits : u32, // a point is distinguished when the lowest bits of its abscissa are zero
+    pub checkpoint : Option<PathBuf>,
+    pub checkpoint_interval : usize, // number of new distinguished points between two saves of the table
+}
+
+impl ParallelRhoParameters{
+    pub fn new(threads : usize, distinguished_bits : u32) -> ParallelRhoParameters{
+        ParallelRhoParameters{
+            threads,
+            distinguished_bits,
+            checkpoint: None,
+            checkpoint_interval: 1000,
+        }
+    }
+
+    pub fn with_checkpoint(self, path : PathBuf, interval : usize) -> ParallelRhoParameters{
+        ParallelRhoParameters{
+            checkpoint: Some(path),
+            checkpoint_interval: interval,
+            ..self
+        }
+    }
+
+    fn validate(&self) -> io::Result<()>{
+        let invalid_input = |message| Err(io::Error::new(io::ErrorKind::InvalidInput, message));
+        if self.threads == 0{
+            return invalid_input("parallel rho needs at least one thread");
+        }
+        if self.distinguished_bits > MAX_DISTINGUISHED_BITS{
+            return invalid_input("too many distinguished bits");
+        }
+        if self.checkpoint.is_some() && self.checkpoint_interval == 0{
+            return invalid_input("the checkpoint interval must be positive");
+        }
+        Ok(())
+    }
+}
+
+// Table of the distinguished points found so far, indexed by abscissa (None for the infinite point)
+struct DistinguishedTable<Integer>{
+    multipliers : Vec<(Integer, Integer)>,
+    points : HashMap<Option<Integer>, (Integer, Integer)>,
+}
+
+fn invalid_data<E : fmt::Debug>(e : E) -> io::Error{
+    io::Error::new(io::ErrorKind::InvalidData, format!("{:?}", e))
+}
+
+fn parse_integer<Integer : IntegerTrait>(s : Option<&str>) -> io::Result<Integer>
+    where <Integer as FromStr>::Err : fmt::Debug{
+    s.ok_or_else(|| invalid_data("missing integer"))?.parse().map_err(invalid_data)
+}
+
+impl<Integer : IntegerTrait> DistinguishedTable<Integer>
+    where <Integer as FromStr>::Err : fmt::Debug{
+
+    // File format: a header line "n r", a line describing the instance (field cardinal, curve, p and q),
+    // r lines "a b" for the multipliers of the walk, then one line "x a b" (or "inf a b") per distinguished point
+    fn save(&self, path : &PathBuf, n : &Integer, instance : &str) -> io::Result<()>{
+        let tmp_path = path.with_extension("tmp");
+        let mut file = io::BufWriter::new(fs::File::create(&tmp_path)?);
+        writeln!(file, "{} {}", n, self.multipliers.len())?;
+        writeln!(file, "{}", instance)?;
+        for (a, b) in &self.multipliers{
+            writeln!(file, "{} {}", a, b)?;
+        }
+        for (x, (a, b)) in &self.points{
+            match x{
+                Some(x) => writeln!(file, "{} {} {}", x, a, b)?,
+                None => writeln!(file, "inf {} {}", a, b)?,
+            }
+        }
+        file.flush()?;
+        fs::rename(tmp_path, path)
+    }
+
+    fn load(path : &PathBuf, n : &Integer, instance : &str) -> io::Result<DistinguishedTable<Integer>>{
+        let mut lines = BufReader::new(fs::File::open(path)?).lines();
+        let header = lines.next().ok_or_else(|| invalid_data("empty checkpoint"))??;
+        let mut header = header.split_whitespace();
+        if &parse_integer::<Integer>(header.next())? != n{
+            return Err(invalid_data("checkpoint of another discrete logarithm"));
+        }
+        let partitions : usize = header.next().ok_or_else(|| invalid_data("missing partitions"))?.parse().map_err(invalid_data)?;
+        if lines.next().ok_or_else(|| invalid_data("missing instance"))?? != instance{
+            return Err(invalid_data("checkpoint of another discrete logarithm"));
+        }
+
+        let mut table = DistinguishedTable{
+            multipliers: Vec::new(),
+            points: HashMap::new(),
+        };
+        for line in lines{
+            let line = line?;
+            let mut words = line.split_whitespace();
+            if table.multipliers.len() < partitions{
+                table.multipliers.push((parse_integer(words.next())?, parse_integer(words.next())?));
+            }else{
+                let x = match words.next(){
+                    Some("inf") => None,
+                    x => Some(parse_integer(x)?),
+                };
+                table.points.insert(x, (parse_integer(words.next())?, parse_integer(words.next())?));
+            }
+        }
+        Ok(table)
+    }
+}
+
+impl<K> EllipticCurve<K>
+    where K : FiniteField + fmt::Display + Send + Sync, K::Integer : Send + Sync, <K::Integer as FromStr>::Err : fmt::Debug{
+
+        // Representative of the class {x, -x} with the smallest ordinate
+        fn canonical(&self, x : WalkPoint<K>, n : &K::Integer) -> WalkPoint<K>{
+            let y = match &x.point{
+                ProjKPoint::InfPoint => return x,
+                ProjKPoint::FinPoint(_, y) => y.to_integer(),
+            };
+            let neg = self.neg_point(x.point.clone());
+            match &neg{
+                ProjKPoint::FinPoint(_, y_neg) if y_neg.to_integer() < y => WalkPoint{
+                    point: neg,
+                    a: modulo(-x.a, n),
+                    b: modulo(-x.b, n),
+                },
+                _ => x,
+            }
+        }
+
+        // Step of the r-adding walk on the classes {x, -x}. When the next point falls in the same partition,
+        // the following partitions are tried in order to avoid most fruitless 2-cycles
+        fn negation_step(&self, x : &WalkPoint<K>, steps : &[WalkPoint<K>], n : &K::Integer) -> WalkPoint<K>{
+            let r = steps.len();
+            let j = Self::partition(&x.point, r as u64);
+            let mut next = None;
+            for k in 0..LOOK_AHEAD{
+                let step = &steps[(j+k)%r];
+                let y = self.canonical(WalkPoint{
+                    point: self.add_points(x.point.clone(), step.point.clone()),
+                    a: modulo(x.a.clone() + step.a.clone(), n),
+                    b: modulo(x.b.clone() + step.b.clone(), n),
+                }, n);
+                if Self::partition(&y.point, r as u64) != (j+k)%r{
+                    return y;
+                }
+                if next.is_none(){
+                    next = Some(y);
+                }
+            }
+            next.unwrap()
+        }
+
+        fn is_distinguished(point : &ProjKPoint<K>, bits : u32) -> bool{
+            match point{
+                ProjKPoint::InfPoint => true,
+                ProjKPoint::FinPoint(x, _) => x.to_integer()%(1u64 << bits) == K::Integer::from(0),
+            }
+        }
+
+        // Field, curve and points of the discrete logarithm, which a checkpoint must match to be resumed
+        fn instance_description(&self, p : &ProjKPoint<K>, q : &ProjKPoint<K>) -> String{
+            format!("{} | {} | {} | {}", K::cardinal(), self, p, q)
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        fn distinguished_walks(&self, steps : &[WalkPoint<K>], p : &ProjKPoint<K>, q : &ProjKPoint<K>, n : &K::Integer,
+            distinguished_bits : u32, stop : &AtomicBool, sender : mpsc::Sender<WalkPoint<K>>){
+            let max_length = 20u64 << distinguished_bits; // walks stuck in a fruitless cycle are abandoned
+            while !stop.load(Ordering::Relaxed){
+                let mut x = self.canonical(self.random_walk_point(p, q, n), n);
+                for _i in 0..max_length{
+                    if stop.load(Ordering::Relaxed){
+                        return;
+                    }
+                    if Self::is_distinguished(&x.point, distinguished_bits){
+                        if sender.send(x).is_err(){
+                            return;
+                        }
+                        break;
+                    }
+                    x = self.negation_step(&x, steps, n);
+                }
+            }
+        }
+
+        // Parallel Pollard rho with distinguished points and the negation map: return m such that q = [m]p,
+        // where p has prime order n and q lies in the subgroup generated by p.
+        // The table of distinguished points is saved to (and resumed from) the checkpoint file if any
+        pub fn ecdlp_parallel_rho(&self, p : &ProjKPoint<K>, q : &ProjKPoint<K>, n : K::Integer, parameters : &ParallelRhoParameters)
+            -> io::Result<K::Integer>{
+            parameters.validate()?;
+            let instance = self.instance_description(p, q);
+            let mut table = match &parameters.checkpoint{
+                Some(path) if path.exists() => DistinguishedTable::load(path, &n, &instance)?,
+                _ => DistinguishedTable{
+                    multipliers: (0..PARALLEL_RHO_PARTITIONS).map(|_| {
+                        let x = self.random_walk_point(p, q, &n);
+                        (x.a, x.b)
+                    }).collect(),
+                    points: HashMap::new(),
+                },
+            };
+            let steps : Vec<WalkPoint<K>> = table.multipliers.iter().map(|(a, b)| WalkPoint{
+                point: self.linear_combination(a.clone(), p, b.clone(), q),
+                a: a.clone(),
+                b: b.clone(),
+            }).collect();
+
+            let stop = AtomicBool::new(false);
+            let (sender, receiver) = mpsc::channel();
+
+            thread::scope(|scope| {
+                for _i in 0..parameters.threads{
+                    let sender = sender.clone();
+                    let (steps, n, stop) = (&steps, &n, &stop);
+                    scope.spawn(move || self.distinguished_walks(steps, p, q, n, parameters.distinguished_bits, stop, sender));
+                }
+                drop(sender);
+
+                let mut new_points = 0;
+                let result = loop{
+                    let x = receiver.recv().expect("every rho thread stopped");
+                    let key = match &x.point{
+                        ProjKPoint::InfPoint => None,
+                        ProjKPoint::FinPoint(x, _) => Some(x.to_integer()),
+                    };
+                    if let Some((a, b)) = table.points.get(&key){
+                        let y = WalkPoint{
+                            point: x.point.clone(),
+                            a: a.clone(),
+                            b: b.clone(),
+                        };
+                        if let Some(m) = self.solve_collision(&x, &y, p, q, &n){
+                            break Ok(m);
+                        }
+                    }else if let ProjKPoint::InfPoint = x.point{ // [a]p + [b]q = O
+                        let origin = WalkPoint{
+                            point: ProjKPoint::InfPoint,
+                            a: K::Integer::from(0),
+                            b: K::Integer::from(0),
+                        };
+                        if let Some(m) = self.solve_collision(&x, &origin, p, q, &n){
+                            break Ok(m);
+                        }
+                    }
+                    table.points.insert(key, (x.a, x.b));
+
+                    new_points += 1;
+                    if let Some(path) = &parameters.checkpoint{
+                        if new_points%parameters.checkpoint_interval == 0{
+                            if let Err(e) = table.save(path, &n, &instance){
+                                break Err(e);
+                            }
+                        }
+                    }
+                };
+                stop.store(true, Ordering::Relaxed);
+                result
+            }).and_then(|m| {
+                // the last table is kept, so that a later run on the same instance finishes at once
+                if let Some(path) = &parameters.checkpoint{
+                    table.save(path, &n, &instance)?;
+                }
+                Ok(m)
+            })
+        }
+}
@@ -0,0 +1,238 @@
+use super::*;
+
+use crate::finite_fields::*;
+use crate::finite_fields::runtime::{FpContext, RuntimeFp};
+use crate::integers::is_probable_prime;
+
+pub type Integer = gmp::mpz::Mpz;
+
+const P : u32 = 10169;
+
+declare_finite_field!(K, Integer, Integer::from(P), m10169);
+
+fn point_order(ell : &EllipticCurve<K>, p : &ProjKPoint<K>) -> u32{
+    let mut order = 1;
+    let mut q = p.clone();
+    while q != ProjKPoint::InfPoint{
+        q = ell.add_points(q, p.clone());
+        order += 1;
+    }
+    order
+}
+
+// A random curve together with a point of prime order
+fn sample_prime_order_point(min_order : u32) -> (EllipticCurve<K>, ProjKPoint<K>, u32){
+    loop{
+        let a = K::new(Integer::sample_uniform(&Integer::from(0), &Integer::from(P)));
+        let b = K::new(Integer::sample_uniform(&Integer::from(0), &Integer::from(P)));
+        let ell = EllipticCurve::new_reduced_weierstrass(a, b);
+        if ell.discriminant() == K::from_int(0){
+            continue;
+        }
+        let p = ell.sample_point();
+        let n = point_order(&ell, &p);
+        if n >= min_order && is_probable_prime(&Integer::from(n)){
+            return (ell, p, n);
+        }
+    }
+}
+
+#[test]
+fn rho_solves_dlp(){
+    for _i in 1..3{
+        let (ell, p, n) = sample_prime_order_point(1000);
+        for _j in 1..5{
+            let m = Integer::sample_uniform(&Integer::from(0), &Integer::from(n));
+            let q = ell.scalar_mult(m.clone(), p.clone());
+
+            assert_eq!(ell.ecdlp_rho(&p, &q, Integer::from(n)), Some(m));
+        }
+    }
+}
+
+#[test]
+fn rho_infinite_point(){
+    let (ell, p, n) = sample_prime_order_point(1000);
+    assert_eq!(ell.ecdlp_rho(&p, &ProjKPoint::InfPoint, Integer::from(n)), Some(Integer::from(0)));
+}
+
+#[test]
+fn parallel_rho_solves_dlp(){
+    let (ell, p, n) = sample_prime_order_point(1000);
+    let parameters = parallel_rho::ParallelRhoParameters::new(4, 2);
+    for _j in 1..5{
+        let m = Integer::sample_uniform(&Integer::from(0), &Integer::from(n));
+        let q = ell.scalar_mult(m.clone(), p.clone());
+
+        assert_eq!(ell.ecdlp_parallel_rho(&p, &q, Integer::from(n), &parameters).unwrap(), m);
+    }
+}
+
+// Removes the checkpoint file even when an assertion fails
+struct RemoveOnDrop(std::path::PathBuf);
+
+impl Drop for RemoveOnDrop{
+    fn drop(&mut self){
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+#[test]
+fn parallel_rho_checkpoint(){
+    let (ell, p, n) = sample_prime_order_point(1000);
+    let path = RemoveOnDrop(std::env::temp_dir().join(format!("rho_checkpoint_{}", rand::random::<u64>())));
+    let parameters = parallel_rho::ParallelRhoParameters::new(2, 1).with_checkpoint(path.0.clone(), 1);
+
+    let m = Integer::sample_uniform(&Integer::from(1), &Integer::from(n));
+    let q = ell.scalar_mult(m.clone(), p.clone());
+
+    assert_eq!(ell.ecdlp_parallel_rho(&p, &q, Integer::from(n), &parameters).unwrap(), m);
+    assert!(path.0.exists());
+    // resume from the saved table
+    assert_eq!(ell.ecdlp_parallel_rho(&p, &q, Integer::from(n), &parameters).unwrap(), m);
+
+    // a checkpoint of another instance of the same order is rejected
+    let other_q = ell.scalar_mult(Integer::from(n) - m, p.clone());
+    let error = ell.ecdlp_parallel_rho(&p, &other_q, Integer::from(n), &parameters).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn parallel_rho_rejects_invalid_parameters(){
+    let (ell, p, n) = sample_prime_order_point(1000);
+    let invalid = [
+        parallel_rho::ParallelRhoParameters::new(0, 2),
+        parallel_rho::ParallelRhoParameters::new(2, 64),
+        parallel_rho::ParallelRhoParameters::new(2, 2).with_checkpoint(std::env::temp_dir().join("unused_rho_checkpoint"), 0),
+    ];
+    for parameters in &invalid{
+        let error = ell.ecdlp_parallel_rho(&p, &p, Integer::from(n), parameters).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+}
+
+#[test]
+fn kangaroo_solves_interval_dlp(){
+    let (ell, p, n) = sample_prime_order_point(1000);
+    for _j in 1..10{
+        let a = Integer::sample_uniform(&Integer::from(0), &Integer::from(n - 300));
+        let b = a.clone() + Integer::from(300);
+        let m = Integer::sample_uniform(&a, &b);
+        let q = ell.scalar_mult(m.clone(), p.clone());
+
+        assert_eq!(ell.ecdlp_kangaroo(&p, &q, a.clone(), b.clone()), Some(m));
+        assert_eq!(ell.ecdlp_kangaroo(&p, &ell.scalar_mult(a.clone(), p.clone()), a.clone(), b.clone()), Some(a));
+    }
+}
+
+fn sample_curve() -> EllipticCurve<K>{
+    let mut ell = EllipticCurve::new_reduced_weierstrass(K::from_int(0), K::from_int(0));
+    while ell.discriminant() == K::from_int(0){
+        let a = K::new(Integer::sample_uniform(&Integer::from(0), &Integer::from(P)));
+        let b = K::new(Integer::sample_uniform(&Integer::from(0), &Integer::from(P)));
+        ell = EllipticCurve::new_reduced_weierstrass(a, b);
+    }
+    ell
+}
+
+#[test]
+fn point_order_works(){
+    for _i in 1..5{
+        let ell = sample_curve();
+        let p = ell.sample_point();
+        assert_eq!(ell.point_order(&p), Integer::from(point_order(&ell, &p)));
+    }
+}
+
+// Number of points of y^2 = x^3 + ax + b, from the Legendre symbols of the right hand sides
+fn curve_cardinal(ell : &EllipticCurve<K>) -> Integer{
+    let mut cardinal = Integer::from(P + 1);
+    for x in 0..P as i32{
+        let x = K::from_int(x);
+        let rhs = x.clone()*x.clone()*x.clone() + ell.a_4.clone()*x + ell.a_6.clone();
+        cardinal += Integer::from(i32::from(rhs.legendre_symbol()));
+    }
+    cardinal
+}
+
+#[test]
+fn point_order_from_the_cardinal(){
+    for _i in 1..5{
+        let ell = sample_curve();
+        let cardinal = curve_cardinal(&ell);
+        let p = ell.sample_point();
+        let q = ell.scalar_mult(Integer::from(1234), p.clone());
+        assert_eq!(ell.point_order_dividing(&p, cardinal.clone()), Integer::from(point_order(&ell, &p)));
+        assert_eq!(ell.scalar_mult(ell.discrete_log_dividing(&p, &q, cardinal).unwrap(), p.clone()), q);
+    }
+}
+
+#[test]
+#[should_panic(expected = "must be positive")]
+fn point_order_dividing_zero_panics(){
+    let ell = sample_curve();
+    ell.point_order_dividing(&ell.sample_point(), Integer::from(0));
+}
+
+#[test]
+fn point_order_over_a_large_field(){
+    // the exhaustive search of the Hasse interval would take 2^21 additions
+    let mut p = (Integer::from(1) << 40) + Integer::from(1);
+    while !is_probable_prime(&p){
+        p += Integer::from(2);
+    }
+    let context = FpContext::new(p);
+    RuntimeFp::with_context(&context, || {
+        let ell = EllipticCurve::new_reduced_weierstrass(RuntimeFp::<Integer>::from_int(3), RuntimeFp::from_int(7));
+        let point = ell.sample_point();
+        let order = ell.point_order(&point);
+        assert_eq!(ell.scalar_mult(order.clone(), point.clone()), ProjKPoint::InfPoint);
+        for (l, _e) in crate::integers::factor(&order){
+            assert_ne!(ell.scalar_mult(order.clone()/l, point.clone()), ProjKPoint::InfPoint);
+        }
+    });
+}
+
+#[test]
+fn pohlig_hellman_solves_dlp(){
+    for _i in 1..5{
+        let ell = sample_curve();
+        let p = ell.sample_point();
+        let order = ell.point_order(&p);
+        for _j in 1..5{
+            let m = Integer::sample_uniform(&Integer::from(0), &order);
+            let q = ell.scalar_mult(m.clone(), p.clone());
+
+            assert_eq!(ell.discrete_log(&p, &q), Some(m));
+        }
+    }
+}
+
+#[test]
+fn bsgs_solves_dlp(){
+    for _i in 1..5{
+        let ell = sample_curve();
+        let p = ell.sample_point();
+        let order = Integer::from(point_order(&ell, &p));
+        for _j in 1..5{
+            let m = Integer::sample_uniform(&Integer::from(0), &order);
+            let q = ell.scalar_mult(m.clone(), p.clone());
+
+            assert_eq!(ell.ecdlp_bsgs(&p, &q, order.clone()), Some(m));
+        }
+    }
+}
+
+#[test]
+fn points_as_map_keys(){
+    let ell = sample_curve();
+    let p = ell.sample_point();
+    let mut multiples = std::collections::HashSet::new();
+    let mut q = ProjKPoint::InfPoint;
+    for _i in 0..point_order(&ell, &p){
+        assert!(multiples.insert(q.clone()));
+        q = ell.add_points(q, p.clone());
+    }
+    assert!(multiples.contains(&ell.scalar_mult(Integer::from(7), p)));
+    assert!(!multiples.insert(ProjKPoint::InfPoint));
+}
@@ -2,7 +2,8 @@ use crate::finite_fields::*;
 
 use super::*;
 
-use crate::field::IntegerTrait;
+use crate::field::{IntegerTrait, map_with_inverses};
+use crate::integers::bit_length;
 
 #[derive(Clone, Copy, Debug)]
 pub struct UnsignedProjPoint<K : FiniteField>{
@@ -32,6 +33,12 @@ impl<K : FiniteField> UnsignedProjPoint<K> {
         }
     }
 
+    // Exchange self and other when choice is 1, with the conditional swaps of the field
+    pub fn conditional_swap(&mut self, other : &mut UnsignedProjPoint<K>, choice : u64){
+        K::conditional_swap(&mut self.x, &mut other.x, choice);
+        K::conditional_swap(&mut self.z, &mut other.z, choice);
+    }
+
     pub fn normalize(self) -> UnsignedProjPoint<K>{
         if self.z == K::from_int(0){
             Self::infinite_point()
@@ -39,6 +46,20 @@ impl<K : FiniteField> UnsignedProjPoint<K> {
             Self::finite_point(self.x/self.z)
         }
     }
+
+    // Normalizes all the points with a single inversion
+    pub fn normalize_batch(points : &mut [UnsignedProjPoint<K>]){
+        let normalized = map_with_inverses(points, |point| point.z.clone(), |point, z_inv| {
+            if point.z == K::from_int(0){
+                Self::infinite_point()
+            }else{
+                Self::finite_point(point.x.clone()*z_inv)
+            }
+        });
+        for (point, normalized) in points.iter_mut().zip(normalized){
+            *point = normalized;
+        }
+    }
 }
 
 impl<K> PartialEq for UnsignedProjPoint<K> where K : FiniteField {
@@ -92,6 +113,14 @@ impl<K> EllipticCurve<K>
                 panic!("sample_point point must be used with curves with only y^2");
             }
             let sample_element = | |{ K::new(K::Integer::sample_uniform(&K::Integer::from(0), &(K::cardinal()-K::Integer::from(1)))) };
+            self.sample_point_with(sample_element)
+        }
+
+        // Sample a point whose abscissa is drawn with sample_element
+        pub fn sample_point_with<F : Fn() -> K>(&self, sample_element : F) -> ProjKPoint<K>{
+            if !self.left_side_empty(){
+                panic!("sample_point point must be used with curves with only y^2");
+            }
             let mut x = sample_element();
 
             let f = |x : K| {
@@ -128,7 +157,12 @@ impl<K> EllipticCurve<K>
         }
 
         pub fn x_dbl(&self, p : UnsignedProjPoint<K>) -> UnsignedProjPoint<K>{
-            let a = self.a_2.clone();
+            let a24 = (self.a_2.clone() + K::from_int(2))/K::from_int(4);
+            Self::x_dbl_a24(p, &a24)
+        }
+
+        // Doubling with a24 = (a + 2)/4 computed once
+        fn x_dbl_a24(p : UnsignedProjPoint<K>, a24 : &K) -> UnsignedProjPoint<K>{
             let mut q = p.x.clone() + p.z.clone();
             q = q.clone()*q;
 
@@ -139,59 +173,40 @@ impl<K> EllipticCurve<K>
 
             UnsignedProjPoint{
                 x: q*r.clone(),
-                z: s.clone()*(r + s*(a + K::from_int(2))/K::from_int(4) )
+                z: s.clone()*(r + s*a24.clone()),
             }
         }
 
         pub fn scalar_mult_unsigned(&self, n : K::Integer, point : UnsignedProjPoint<K>) -> UnsignedProjPoint<K>{
-
-            if n == K::Integer::from(0){
-                return UnsignedProjPoint::infinite_point();
-            }
-
             if n < K::Integer::from(0){
                 return self.scalar_mult_unsigned(-n, point);
             }
-            
-            let mut logm = 0;
-            let mut m = n.clone();
-            while m != K::Integer::from(0){
-                m >>= 1;
-                logm += 1;
-            }
+            let bits = bit_length(&n);
+            self.montgomery_ladder(&n, bits, point)
+        }
 
+        // Montgomery ladder over the bits lowest bits of n >= 0, as in RFC 7748: the same field operations and
+        // conditional swaps run whatever n, so that the running time only depends on bits when the operations
+        // of the field do not branch on the data
+        pub fn montgomery_ladder(&self, n : &K::Integer, bits : usize, point : UnsignedProjPoint<K>) -> UnsignedProjPoint<K>{
+            let a24 = (self.a_2.clone() + K::from_int(2))/K::from_int(4);
             let mut x0 = UnsignedProjPoint::infinite_point();
             let mut x1 = point.clone();
+            let mut swap = 0;
 
-            let cond_swap = |b: K, x0: UnsignedProjPoint<K>, x1: UnsignedProjPoint<K>| {
-                (UnsignedProjPoint{
-                    x: (K::from_int(1)-b.clone())*x0.x.clone() + b.clone()*x1.x.clone(), 
-                    z: (K::from_int(1)-b.clone())*x0.z.clone() + b.clone()*x1.z.clone(), 
-                }, 
-                UnsignedProjPoint{
-                    x: (K::from_int(1)-b.clone())*x1.x.clone() + b.clone()*x0.x.clone(),
-                    z: (K::from_int(1)-b.clone())*x1.z.clone() + b.clone()*x0.z.clone(),
-                })
-            };
-
-            while logm >= 1{
-                let bit = K::new((n.clone()&(K::Integer::from(1)<<(logm-1)))>>(logm-1)); // the current bit
-                logm -= 1;
-
-                let  (mut a, mut b) = cond_swap(bit.clone(), x0, x1);
+            for t in (0..bits).rev(){
+                let bit = ((n.clone() >> t) & K::Integer::from(1)).to_u64().unwrap(); // the current bit
+                swap ^= bit;
+                x0.conditional_swap(&mut x1, swap);
+                swap = bit;
 
-                b = self.x_add(a.clone(), b, point.clone());
-                a = self.x_dbl(a);
-
-                let (_a, _b) = cond_swap(bit, a, b);
-                x0 = _a;
-                x1 = _b;
+                x1 = self.x_add(x0.clone(), x1, point.clone());
+                x0 = Self::x_dbl_a24(x0, &a24);
             }
-            x0            
+            x0.conditional_swap(&mut x1, swap);
+            x0
         }
-
-
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "gmp"))]
 mod test;
\ No newline at end of file
@@ -70,4 +70,22 @@ fn scalar_multiplication_coincide_scalar(){
 
         }
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn normalize_batch_coincide(){
+    let ell = sample_montgomery();
+    let mut points : Vec<UnsignedProjPoint<K>> = (0..30).map(|_i| {
+        let z = K::new(Integer::sample_uniform(&Integer::from(1), &Integer::from(P)));
+        let p = EllipticCurve::unsigne_point(ell.sample_point());
+        UnsignedProjPoint{x: p.x*z.clone(), z: p.z*z}
+    }).collect();
+    points.push(UnsignedProjPoint::infinite_point());
+    let expected : Vec<UnsignedProjPoint<K>> = points.iter().map(|p| p.clone().normalize()).collect();
+
+    UnsignedProjPoint::normalize_batch(&mut points);
+    for (p, q) in points.iter().zip(expected.iter()){
+        assert_eq!(p.x, q.x);
+        assert_eq!(p.z, q.z);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::field::IntegerTrait;
+use crate::finite_fields::FiniteField;
+use crate::integers::modulo;
+
+use super::*;
+use super::projective::JacobianPoint;
+
+// Vectors (a_1, b_1), (a_2, b_2)
+pub type LatticeBasis<Integer> = ((Integer, Integer), (Integer, Integer));
+
+// Endomorphism acting as [lambda] on the subgroup of prime order n. On the reduced form y^2 = x^3 + a*x + b,
+// reached by x' = x + r, y' = y + s*x + t, it is (x', y') -> (x_factor*x', y_factor*y'):
+// (beta*x, y) with beta^3 = 1 when j = 0, and (-x, i*y) with i^2 = -1 when j = 1728.
+// The basis (a_1, b_1), (a_2, b_2) of the lattice {(a, b) : a + b*lambda = 0 mod n} is made of short vectors
+#[derive(Debug, Clone)]
+pub struct GlvEndomorphism<K : Field>{
+    pub x_factor : K,
+    pub y_factor : K,
+    pub change_of_variables : (K, K, K), // (r, s, t), zero for a curve in reduced form
+    pub lambda : K::Integer,
+    pub order : K::Integer,
+    pub basis : LatticeBasis<K::Integer>,
+}
+
+// round(a/n) for n > 0
+fn round_div<Integer : IntegerTrait>(a : Integer, n : &Integer) -> Integer{
+    let a = Integer::from(2)*a + n.clone();
+    let n2 = Integer::from(2)*n.clone();
+    (a.clone() - modulo(a, &n2))/n2
+}
+
+// Root of unity of the given order, which must divide n-1, modulo the prime n
+fn integer_root_of_unity<Integer : IntegerTrait>(n : &Integer, order : u32) -> Integer{
+    loop{
+        let h = Integer::sample_uniform(&Integer::from(2), n);
+        let root = h.powm(&((n.clone() - Integer::from(1))/Integer::from(order)), n);
+        if root.powm(&Integer::from(order/2), n) != Integer::from(1){
+            return root;
+        }
+    }
+}
+
+fn field_root_of_unity<K : FiniteField>(order : u32) -> K{
+    loop{
+        let g = K::new(K::Integer::sample_uniform(&K::Integer::from(2), &K::cardinal()));
+        let root = K::exp(g, (K::cardinal() - K::Integer::from(1))/K::Integer::from(order));
+        if K::exp(root.clone(), K::Integer::from(order/2)) != K::from_int(1){
+            return root;
+        }
+    }
+}
+
+// Short basis of the GLV lattice with the extended Euclidean algorithm on (n, lambda)
+fn lattice_basis<Integer : IntegerTrait>(n : &Integer, lambda : &Integer) -> LatticeBasis<Integer>{
+    let mut remainders = vec![(n.clone(), Integer::from(0)), (lambda.clone(), Integer::from(1))]; // (r_i, t_i)
+    while remainders[remainders.len()-1].0 != Integer::from(0){
+        let i = remainders.len() - 1;
+        let (r_prev, t_prev) = remainders[i-1].clone();
+        let (r, t) = remainders[i].clone();
+        let q = r_prev.clone()/r.clone();
+        remainders.push((r_prev - q.clone()*r, t_prev - q*t));
+    }
+    // last remainder at least sqrt(n)
+    let l = remainders.iter().rposition(|(r, _)| r.clone()*r.clone() >= n.clone()).unwrap();
+    let norm = |(r, t) : &(Integer, Integer)| r.clone()*r.clone() + t.clone()*t.clone();
+
+    let (a_1, t_1) = remainders[l+1].clone();
+    let (a_2, t_2) = match remainders.get(l+2){
+        Some(v) if norm(v) < norm(&remainders[l]) => v.clone(),
+        _ => remainders[l].clone(),
+    };
+    ((a_1, -t_1), (a_2, -t_2))
+}
+
+impl<K : FiniteField> GlvEndomorphism<K>{
+    pub fn apply(&self, point : &ProjKPoint<K>) -> ProjKPoint<K>{
+        let (r, s, t) = self.change_of_variables.clone();
+        match point{
+            ProjKPoint::InfPoint => ProjKPoint::InfPoint,
+            ProjKPoint::FinPoint(x, y) => {
+                let (x_reduced, y_reduced) = (x.clone() + r.clone(), y.clone() + s.clone()*x.clone() + t.clone());
+                let x_image = self.x_factor.clone()*x_reduced - r;
+                let y_image = self.y_factor.clone()*y_reduced - s*x_image.clone() - t;
+                ProjKPoint::FinPoint(x_image, y_image)
+            }
+        }
+    }
+
+    // (k_1, k_2) with k = k_1 + k_2*lambda mod n, both of size about sqrt(n)
+    pub fn decompose(&self, k : &K::Integer) -> (K::Integer, K::Integer){
+        let ((a_1, b_1), (a_2, b_2)) = self.basis.clone();
+        // (k, 0) = c_1*(a_1, b_1) + c_2*(a_2, b_2) over the rationals, where the determinant of the basis is +-n
+        let sign = if a_1.clone()*b_2.clone() > a_2.clone()*b_1.clone() { K::Integer::from(1) } else { K::Integer::from(-1) };
+        let c_1 = round_div(sign.clone()*b_2.clone()*k.clone(), &self.order);
+        let c_2 = round_div(-sign*b_1.clone()*k.clone(), &self.order);
+        (k.clone() - c_1.clone()*a_1 - c_2.clone()*a_2, -c_1*b_1 - c_2*b_2)
+    }
+}
+
+impl<K> EllipticCurve<K>
+    where K : FiniteField{
+
+        // Endomorphism of the curve acting on the subgroup generated by point, of prime order n,
+        // None if the curve has not j = 0 or j = 1728 or if the required roots of unity do not exist
+        pub fn glv_endomorphism(&self, point : &ProjKPoint<K>, n : &K::Integer) -> Option<GlvEndomorphism<K>>{
+            // the change of variables to the reduced form divides by 2 and 3
+            if self.discriminant() == K::from_int(0) || K::cardinal()%2 == K::Integer::from(0) || K::cardinal()%3 == K::Integer::from(0){
+                return None;
+            }
+            let one = K::Integer::from(1);
+            let j = self.j_invariant();
+            let order = if j == K::from_int(0){
+                3
+            }else if j == K::from_int(1728){
+                4
+            }else{
+                return None;
+            };
+            if (K::cardinal() - one.clone())%(order as u64) != K::Integer::from(0) || (n.clone() - one)%(order as u64) != K::Integer::from(0){
+                return None;
+            }
+
+            let root : K = field_root_of_unity(order);
+            let (x_factor, y_factor) = if order == 3 { (root, K::from_int(1)) } else { (K::from_int(-1), root) };
+            let lambda = integer_root_of_unity(n, order);
+            // the other eigenvalue is lambda^2 when j = 0 and -lambda when j = 1728
+            let other_lambda = if order == 3 { modulo(lambda.clone()*lambda.clone(), n) } else { n.clone() - lambda.clone() };
+
+            let change_of_variables = (self.b2()/K::from_int(12), self.a_1.clone()/K::from_int(2), self.a_3.clone()/K::from_int(2));
+
+            let mut glv = GlvEndomorphism{
+                x_factor,
+                y_factor,
+                change_of_variables,
+                basis: lattice_basis(n, &lambda),
+                lambda,
+                order: n.clone(),
+            };
+            let image = glv.apply(point);
+            if image != self.scalar_mult(glv.lambda.clone(), point.clone()){
+                if image != self.scalar_mult(other_lambda.clone(), point.clone()){
+                    return None;
+                }
+                glv.basis = lattice_basis(n, &other_lambda);
+                glv.lambda = other_lambda;
+            }
+            Some(glv)
+        }
+
+        // [k]point as [k_1]point + [k_2]phi(point), with scalars of half size sharing their doublings
+        pub fn scalar_mult_glv(&self, glv : &GlvEndomorphism<K>, k : K::Integer, point : ProjKPoint<K>) -> JacobianPoint<K>{
+            let (k_1, k_2) = glv.decompose(&k);
+            let image = glv.apply(&point);
+            if self.is_reduced_weierstrass(){
+                return self.multi_scalar_mult_jacobian(&[(k_1, point), (k_2, image)]);
+            }
+
+            // computed on the reduced form y'^2 = x'^3 - c4/48*x' - c6/864, then brought back with
+            // X = X' - r*Z^2 and Y = Y' - s*X*Z - t*Z^3
+            let (r, s, t) = glv.change_of_variables.clone();
+            let reduced = EllipticCurve::new_reduced_weierstrass(-self.c4()/K::from_int(48), -self.c6()/K::from_int(864));
+            let to_reduced = |point : ProjKPoint<K>| match point{
+                ProjKPoint::InfPoint => ProjKPoint::InfPoint,
+                ProjKPoint::FinPoint(x, y) => ProjKPoint::FinPoint(x.clone() + r.clone(), y + s.clone()*x + t.clone()),
+            };
+            let sum = reduced.multi_scalar_mult_jacobian(&[(k_1, to_reduced(point)), (k_2, to_reduced(image))]);
+            let z2 = sum.z.clone()*sum.z.clone();
+            let x = sum.x - r*z2.clone();
+            let y = sum.y - s*x.clone()*sum.z.clone() - t*z2*sum.z.clone();
+            JacobianPoint{ x, y, z: sum.z }
+        }
+}
+
+#[cfg(all(test, feature = "gmp"))]
+mod test;
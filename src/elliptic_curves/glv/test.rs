@@ -0,0 +1,138 @@
+use super::*;
+
+use crate::finite_fields::*;
+use crate::integers::{factor, isqrt};
+use crate::standard_curves::{secp256k1, FSecp256k1};
+
+pub type Integer = gmp::mpz::Mpz;
+
+const P : u32 = 10177; // = 1 mod 12, so that both j = 0 and j = 1728 curves have an endomorphism over F_p
+
+declare_finite_field!(K, Integer, Integer::from(P), m10177);
+
+fn sample_element() -> K{
+    K::new(Integer::sample_uniform(&Integer::from(1), &Integer::from(P)))
+}
+
+fn curve_order(ell : &EllipticCurve<K>) -> u32{
+    1 + (0..P).map(|x| {
+        let x = K::new(Integer::from(x));
+        let f = x.clone()*x.clone()*x.clone() + ell.a_4.clone()*x + ell.a_6.clone();
+        (1 + f.legendre_symbol()) as u32
+    }).sum::<u32>()
+}
+
+// Point of large prime order n on the curve, with n^2 not dividing its order
+fn sample_prime_order_point(ell : &EllipticCurve<K>) -> Option<(ProjKPoint<K>, Integer)>{
+    let order = Integer::from(curve_order(ell));
+    let (n, e) = factor(&order).into_iter().max()?;
+    if e > 1 || n < Integer::from(100){
+        return None;
+    }
+    let point = ell.scalar_mult(order/n.clone(), ell.sample_point());
+    if point == ProjKPoint::InfPoint{
+        return None;
+    }
+    Some((point, n))
+}
+
+// Curve y^2 = x^3 + b (j = 0) or y^2 = x^3 + a*x (j = 1728) with a point of large prime order n
+fn sample_glv_instance(j_0 : bool) -> (EllipticCurve<K>, ProjKPoint<K>, Integer){
+    loop{
+        let ell = if j_0{
+            EllipticCurve::new_reduced_weierstrass(K::from_int(0), sample_element())
+        }else{
+            EllipticCurve::new_reduced_weierstrass(sample_element(), K::from_int(0))
+        };
+        if let Some((point, n)) = sample_prime_order_point(&ell){
+            return (ell, point, n);
+        }
+    }
+}
+
+fn check_glv(ell : &EllipticCurve<K>, point : &ProjKPoint<K>, n : &Integer){
+    let glv = ell.glv_endomorphism(point, n).unwrap();
+    assert!(ell.is_on_curve(&glv.apply(point)));
+    assert_eq!(glv.apply(point), ell.scalar_mult(glv.lambda.clone(), point.clone()));
+    for _i in 0..20{
+        let k = Integer::sample_uniform(&Integer::from(0), n);
+        let (k_1, k_2) = glv.decompose(&k);
+        assert_eq!(modulo(k_1.clone() + k_2.clone()*glv.lambda.clone(), n), k);
+        assert!(k_1.abs() < Integer::from(4)*isqrt(n) && k_2.abs() < Integer::from(4)*isqrt(n));
+        assert_eq!(ell.scalar_mult_glv(&glv, k.clone(), point.clone()).to_affine(), ell.scalar_mult(k, point.clone()));
+    }
+}
+
+#[test]
+fn glv_j_0(){
+    for _j in 0..10{
+        let (ell, point, n) = sample_glv_instance(true);
+        check_glv(&ell, &point, &n);
+    }
+}
+
+#[test]
+fn glv_j_1728(){
+    for _j in 0..10{
+        let (ell, point, n) = sample_glv_instance(false);
+        check_glv(&ell, &point, &n);
+    }
+}
+
+#[test]
+fn glv_general_weierstrass(){
+    // y'^2 = x'^3 + a*x' + b with x' = x + r and y' = y + s*x + t
+    for j in 0..10{
+        let (reduced, point, n) = sample_glv_instance(j%2 == 0);
+        let (r, s, t) = (sample_element(), sample_element(), sample_element());
+        let (a, b) = (reduced.a_4.clone(), reduced.a_6.clone());
+        let ell = EllipticCurve{
+            a_1: K::from_int(2)*s.clone(),
+            a_3: K::from_int(2)*t.clone(),
+
+            a_2: K::from_int(3)*r.clone() - s.clone()*s.clone(),
+            a_4: K::from_int(3)*r.clone()*r.clone() - K::from_int(2)*s.clone()*t.clone() + a.clone(),
+            a_6: r.clone()*r.clone()*r.clone() + a.clone()*r.clone() + b - t.clone()*t.clone(),
+        };
+        assert!(!ell.is_reduced_weierstrass());
+        let point = match point{
+            ProjKPoint::FinPoint(x, y) => {
+                let x = x - r;
+                let y = y - s*x.clone() - t;
+                ProjKPoint::FinPoint(x, y)
+            },
+            ProjKPoint::InfPoint => unreachable!(),
+        };
+        assert!(ell.is_on_curve(&point));
+        check_glv(&ell, &point, &n);
+    }
+}
+
+#[test]
+fn no_endomorphism(){
+    let ell = EllipticCurve::new_reduced_weierstrass(K::from_int(2), K::from_int(3));
+    assert!(ell.glv_endomorphism(&ell.sample_point(), &Integer::from(P)).is_none());
+}
+
+#[test]
+fn glv_secp256k1(){
+    let parameters = secp256k1();
+    let ell = &parameters.curve;
+    let glv = ell.glv_endomorphism(&parameters.generator, &parameters.order).unwrap();
+    // the endomorphism is one of (beta*x, y) -> [lambda] and (beta^2*x, y) -> [lambda^2]
+    let lambda = Integer::from_str_radix("5363ad4cc05c30e0a5261c028812645a122e22ea20816678df02967c1b23bd72", 16).unwrap();
+    let beta = FSecp256k1::new(Integer::from_str_radix("7ae96a2b657c07106e64479eac3434e99cf0497512f58995c1396c28719501ee", 16).unwrap());
+    if glv.lambda == lambda{
+        assert_eq!(glv.x_factor, beta);
+    }else{
+        assert_eq!(glv.lambda, modulo(lambda.clone()*lambda, &parameters.order));
+        assert_eq!(glv.x_factor, beta.clone()*beta);
+    }
+    for _i in 0..5{
+        let k = Integer::sample_uniform(&Integer::from(0), &parameters.order);
+        let (k_1, k_2) = glv.decompose(&k);
+        assert!(k_1.bit_length() <= 129 && k_2.bit_length() <= 129);
+        assert_eq!(ell.scalar_mult_glv(&glv, k.clone(), parameters.generator.clone()).to_affine(),
+                   ell.scalar_mult(k, parameters.generator.clone()));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::field::IntegerTrait;
+use crate::integers::bit_length;
+
+use super::*;
+use super::projective::JacobianPoint;
+use super::windowed::wnaf;
+
+const STRAUS_MAX_POINTS : usize = 32; // above, the bucket method of Pippenger is used
+const STRAUS_WINDOW : u32 = 4;
+
+impl<K> EllipticCurve<K>
+    where K : Field{
+
+        // Terms with a non negative scalar and a finite point
+        fn normalize_terms(&self, terms : &[(K::Integer, ProjKPoint<K>)]) -> Vec<(K::Integer, ProjKPoint<K>)>{
+            terms.iter().filter(|(_, p)| p != &ProjKPoint::InfPoint).map(|(k, p)| {
+                if k < &K::Integer::from(0){
+                    (-k.clone(), self.neg_point(p.clone()))
+                }else{
+                    (k.clone(), p.clone())
+                }
+            }).collect()
+        }
+
+        // Interleaved wNAF: the doublings are shared between all the terms
+        fn straus(&self, terms : &[(K::Integer, ProjKPoint<K>)]) -> JacobianPoint<K>{
+            let digits : Vec<Vec<i64>> = terms.iter().map(|(k, _)| wnaf(k, STRAUS_WINDOW)).collect();
+            let multiples : Vec<Vec<ProjKPoint<K>>> = terms.iter().map(|(_, p)| self.odd_multiples(p, STRAUS_WINDOW)).collect();
+
+            let length = digits.iter().map(|d| d.len()).max().unwrap_or(0);
+            let mut result = JacobianPoint::infinite_point();
+            for i in (0..length).rev(){
+                result = self.jacobian_double(result);
+                for (d, multiples) in digits.iter().zip(&multiples){
+                    match d.get(i){
+                        Some(digit) if *digit > 0 => {
+                            result = self.jacobian_add_affine(result, &multiples[(*digit as usize - 1)/2]);
+                        },
+                        Some(digit) if *digit < 0 => {
+                            result = self.jacobian_add_affine(result, &self.neg_point(multiples[((-digit) as usize - 1)/2].clone()));
+                        },
+                        _ => (),
+                    }
+                }
+            }
+            result
+        }
+
+        // Bucket method: for each window of c bits, the points are sorted in 2^c - 1 buckets by their digit,
+        // and the sum of digit*bucket is computed with running sums
+        fn pippenger(&self, terms : &[(K::Integer, ProjKPoint<K>)]) -> JacobianPoint<K>{
+            let c = ((usize::BITS - terms.len().leading_zeros()) as usize).saturating_sub(2).max(1);
+            let bits = terms.iter().map(|(k, _)| bit_length(k)).max().unwrap_or(0);
+            let windows = bits.div_ceil(c);
+
+            let mut result = JacobianPoint::infinite_point();
+            for window in (0..windows).rev(){
+                for _i in 0..c{
+                    result = self.jacobian_double(result);
+                }
+                let mut buckets = vec![JacobianPoint::infinite_point(); 1 << c];
+                for (k, p) in terms{
+                    let digit = ((k.clone() >> (window*c))%(1u64 << c)).to_u64().unwrap() as usize;
+                    if digit != 0{
+                        buckets[digit] = self.jacobian_add_affine(buckets[digit].clone(), p);
+                    }
+                }
+                let mut running_sum = JacobianPoint::infinite_point();
+                let mut window_sum = JacobianPoint::infinite_point();
+                for bucket in buckets.into_iter().skip(1).rev(){
+                    running_sum = self.jacobian_add(running_sum, bucket);
+                    window_sum = self.jacobian_add(window_sum, running_sum.clone());
+                }
+                result = self.jacobian_add(result, window_sum);
+            }
+            result
+        }
+
+        // Sum of the [k_i]P_i, with the method of Straus for few points and the one of Pippenger for many
+        pub fn multi_scalar_mult(&self, terms : &[(K::Integer, ProjKPoint<K>)]) -> ProjKPoint<K>{
+            self.multi_scalar_mult_jacobian(terms).to_affine()
+        }
+
+        // multi_scalar_mult without the final inversion
+        pub fn multi_scalar_mult_jacobian(&self, terms : &[(K::Integer, ProjKPoint<K>)]) -> JacobianPoint<K>{
+            for (_, p) in terms{
+                assert!(self.is_on_curve(p));
+            }
+            let terms = self.normalize_terms(terms);
+            if terms.len() <= STRAUS_MAX_POINTS{
+                self.straus(&terms)
+            }else{
+                self.pippenger(&terms)
+            }
+        }
+}
+
+#[cfg(all(test, feature = "gmp"))]
+mod test;
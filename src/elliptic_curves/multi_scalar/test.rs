@@ -0,0 +1,63 @@
+use super::*;
+
+use crate::finite_fields::*;
+
+pub type Integer = gmp::mpz::Mpz;
+
+const P : u32 = 10169;
+
+declare_finite_field!(K, Integer, Integer::from(P), m10169);
+
+fn sample_element() -> K{
+    K::new(Integer::sample_uniform(&Integer::from(0), &Integer::from(P)))
+}
+
+fn sample_curve() -> EllipticCurve<K>{
+    loop{
+        let ell = EllipticCurve::new_reduced_weierstrass(sample_element(), sample_element());
+        if ell.discriminant() != K::from_int(0){
+            return ell;
+        }
+    }
+}
+
+fn naive_multi_scalar_mult(ell : &EllipticCurve<K>, terms : &[(Integer, ProjKPoint<K>)]) -> ProjKPoint<K>{
+    terms.iter().fold(ProjKPoint::InfPoint, |sum, (k, p)| ell.add_points(sum, ell.scalar_mult(k.clone(), p.clone())))
+}
+
+fn sample_terms(ell : &EllipticCurve<K>, count : usize) -> Vec<(Integer, ProjKPoint<K>)>{
+    (0..count).map(|i| {
+        let k = Integer::sample_uniform(&-(Integer::from(1) << 40), &(Integer::from(1) << 40));
+        let p = if i%10 == 0 { ProjKPoint::InfPoint } else { ell.sample_point() };
+        (k, p)
+    }).collect()
+}
+
+#[test]
+fn straus_coincide(){
+    for _j in 0..10{
+        let ell = sample_curve();
+        for count in 0..8{
+            let terms = sample_terms(&ell, count);
+            assert_eq!(ell.multi_scalar_mult(&terms), naive_multi_scalar_mult(&ell, &terms));
+            assert!(ell.multi_scalar_mult_jacobian(&terms) == JacobianPoint::from_affine(ell.multi_scalar_mult(&terms)));
+        }
+    }
+}
+
+#[test]
+fn pippenger_coincide(){
+    for _j in 0..2{
+        let ell = sample_curve();
+        for count in [STRAUS_MAX_POINTS + 1, 100].iter(){
+            let mut terms = sample_terms(&ell, *count);
+            assert_eq!(ell.multi_scalar_mult(&terms), naive_multi_scalar_mult(&ell, &terms));
+
+            // terms which cancel out
+            let (k, p) = terms[1].clone();
+            terms.push((-k, p));
+            terms.push((Integer::from(0), ell.sample_point()));
+            assert_eq!(ell.multi_scalar_mult(&terms), naive_multi_scalar_mult(&ell, &terms));
+        }
+    }
+}
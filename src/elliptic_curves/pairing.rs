@@ -0,0 +1,148 @@
+use crate::finite_fields::FiniteField;
+use crate::finite_fields::quadratic_extension::QuadraticExtension;
+
+use super::*;
+
+impl<K> ProjKPoint<K> where K : FiniteField{
+    pub fn to_quadratic_extension(self) -> ProjKPoint<QuadraticExtension<K>>{
+        use ProjKPoint::*;
+        match self{
+            InfPoint => InfPoint,
+            FinPoint(x, y) => FinPoint(QuadraticExtension::from_base(x), QuadraticExtension::from_base(y)),
+        }
+    }
+}
+
+impl<K> EllipticCurve<K>
+    where K : Field{
+
+        // Value at t of l_{r, s}/v_{r+s}, where l_{r, s} is the line through r and s and v_{r+s} the vertical line through r+s.
+        // Return None if t is a zero or a pole of this function
+        fn miller_step(&self, r : &ProjKPoint<K>, s : &ProjKPoint<K>, t : &ProjKPoint<K>) -> Option<(K, ProjKPoint<K>)>{
+            use ProjKPoint::*;
+            let (x_t, y_t) = match t{
+                FinPoint(x, y) => (x.clone(), y.clone()),
+                InfPoint => panic!("Miller functions must be evaluated at a finite point"),
+            };
+
+            match (r, s){
+                (InfPoint, _) | (_, InfPoint) => Some((K::from_int(1), self.add_points(r.clone(), s.clone()))),
+                (FinPoint(x1, y1), FinPoint(x2, y2)) => {
+                    if r == &self.neg_point(s.clone()){
+                        let vertical = x_t - x1.clone();
+                        if vertical == K::from_int(0){
+                            return None;
+                        }
+                        return Some((vertical, InfPoint));
+                    }
+                    let (lambda, nu) = self.line_coefficients(x1.clone(), y1.clone(), x2.clone(), y2.clone());
+                    let sum = self.add_points(r.clone(), s.clone());
+                    let x3 = match &sum{
+                        FinPoint(x3, _) => x3.clone(),
+                        InfPoint => unreachable!(),
+                    };
+
+                    let line = y_t - lambda*x_t.clone() - nu;
+                    let vertical = x_t - x3;
+                    if line == K::from_int(0) || vertical == K::from_int(0){
+                        return None;
+                    }
+                    Some((line/vertical, sum))
+                }
+            }
+        }
+
+        // Miller's algorithm: value at t of the function f_{n, p} of divisor n(p) - ([n]p) - (n-1)(O)
+        pub fn miller_function(&self, n : K::Integer, p : &ProjKPoint<K>, t : &ProjKPoint<K>) -> Option<K>{
+            assert!(n > K::Integer::from(0));
+
+            let mut logm = 0;
+            let mut m = n.clone();
+            while m != K::Integer::from(0){
+                m >>= 1;
+                logm += 1;
+            }
+            logm -= 1; // the first bit is r = p
+
+            let mut f = K::from_int(1);
+            let mut r = p.clone();
+            while logm >= 1{
+                let bit = (n.clone()&(K::Integer::from(1)<<(logm-1)))>>(logm-1); // the current bit
+                logm -= 1;
+
+                let (g, r2) = self.miller_step(&r, &r, t)?;
+                f = f.clone()*f*g;
+                r = r2;
+                if bit != K::Integer::from(0){
+                    let (g, r2) = self.miller_step(&r, p, t)?;
+                    f *= g;
+                    r = r2;
+                }
+            }
+            Some(f)
+        }
+}
+
+impl<K> EllipticCurve<K>
+    where K : FiniteField + fmt::Display{
+
+        // Smallest k <= max_degree such that n divides q^k - 1, where q is the cardinal of the base field
+        pub fn embedding_degree(&self, n : K::Integer, max_degree : u32) -> Option<u32>{
+            let q = K::cardinal()%n.clone();
+            let mut q_k = q.clone();
+            for k in 1..(max_degree+1){
+                if q_k == K::Integer::from(1){
+                    return Some(k);
+                }
+                q_k = (q_k*q.clone())%n.clone();
+            }
+            None
+        }
+
+        // Reduced Tate pairing of p, a point of order n, with t, both defined over K which must contain the n-th roots of unity
+        pub fn tate_pairing(&self, n : K::Integer, p : &ProjKPoint<K>, t : &ProjKPoint<K>) -> Option<K>{
+            let f = self.miller_function(n.clone(), p, t)?;
+            Some(K::exp(f, (K::cardinal()-K::Integer::from(1))/n))
+        }
+
+        pub fn to_quadratic_extension(&self) -> EllipticCurve<QuadraticExtension<K>>{
+            EllipticCurve{
+                a_1: QuadraticExtension::from_base(self.a_1.clone()),
+                a_3: QuadraticExtension::from_base(self.a_3.clone()),
+
+                a_2: QuadraticExtension::from_base(self.a_2.clone()),
+                a_4: QuadraticExtension::from_base(self.a_4.clone()),
+                a_6: QuadraticExtension::from_base(self.a_6.clone()),
+            }
+        }
+
+        // MOV reduction: for p of prime order n and q = [m]p, return (alpha, beta) with alpha of order n
+        // and beta = alpha^m in the quadratic extension of K. Only embedding degrees 1 and 2 are supported
+        pub fn mov_reduction(&self, p : &ProjKPoint<K>, q : &ProjKPoint<K>, n : K::Integer) 
+            -> Result<(QuadraticExtension<K>, QuadraticExtension<K>), Error>{
+            assert!(p != &ProjKPoint::InfPoint);
+            if self.embedding_degree(n.clone(), 2).is_none(){
+                return Err(Error::UnsupportedEmbeddingDegree);
+            }
+
+            // The points are sampled on the reduced curve, isomorphic to self so that q = [m]p still holds there
+            let reduced = self.clone().to_reduced_weierstrass();
+            let ell = reduced.to_quadratic_extension();
+            let p_ext = self.to_reduced_weierstrass_point(p.clone()).to_quadratic_extension();
+            let q_ext = self.to_reduced_weierstrass_point(q.clone()).to_quadratic_extension();
+
+            loop{
+                let t = ell.sample_point_with(QuadraticExtension::sample_uniform);
+                let alpha = ell.tate_pairing(n.clone(), &p_ext, &t);
+                let beta = ell.tate_pairing(n.clone(), &q_ext, &t);
+                if let (Some(alpha), Some(beta)) = (alpha, beta){
+                    if alpha != QuadraticExtension::from_int(1){
+                        return Ok((alpha, beta));
+                    }
+                }
+            }
+        }
+}
+
+#[cfg(all(test, feature = "gmp"))]
+mod test;
@@ -0,0 +1,106 @@
+use super::*;
+
+use crate::field::*;
+use crate::finite_fields::*;
+
+pub type Integer = gmp::mpz::Mpz;
+
+const P : u32 = 1019; // = 3 mod 4, so y^2 = x^3 + x is supersingular with p+1 = 4*3*5*17 points
+const N : u32 = 17;
+
+declare_finite_field!(K, Integer, Integer::from(P), m1019);
+
+type K2 = QuadraticExtension<K>;
+
+fn supersingular_curve() -> EllipticCurve<K>{
+    EllipticCurve::new_reduced_weierstrass(K::from_int(1), K::from_int(0))
+}
+
+fn sample_order_n_point(ell : &EllipticCurve<K>) -> ProjKPoint<K>{
+    let cofactor = Integer::from((P+1)/N);
+    let mut p = ell.scalar_mult(cofactor.clone(), ell.sample_point());
+    while p == ProjKPoint::InfPoint{
+        p = ell.scalar_mult(cofactor.clone(), ell.sample_point());
+    }
+    p
+}
+
+#[test]
+fn embedding_degree_supersingular(){
+    let ell = supersingular_curve();
+    assert_eq!(ell.embedding_degree(Integer::from(N), 10), Some(2));
+    assert_eq!(ell.embedding_degree(Integer::from(3), 10), Some(2));
+    assert_eq!(ell.embedding_degree(Integer::from(N), 1), None);
+}
+
+#[test]
+fn embedding_degree_one(){
+    // 509 divides p - 1 = 1018
+    let ell = supersingular_curve();
+    assert_eq!(ell.embedding_degree(Integer::from(509), 10), Some(1));
+}
+
+#[test]
+fn tate_pairing_bilinear(){
+    let ell = supersingular_curve();
+    let ell2 = ell.to_quadratic_extension();
+    let p = sample_order_n_point(&ell).to_quadratic_extension();
+    for _i in 1..5{
+        let t = ell2.sample_point_with(K2::sample_uniform);
+        let a = Integer::sample_uniform(&Integer::from(1), &Integer::from(N));
+        let ap = ell2.scalar_mult(a.clone(), p.clone());
+
+        if let (Some(e), Some(e_a)) = (ell2.tate_pairing(Integer::from(N), &p, &t), ell2.tate_pairing(Integer::from(N), &ap, &t)){
+            assert_eq!(K2::exp(e.clone(), Integer::from(N)), K2::from_int(1));
+            assert_eq!(K2::exp(e, a), e_a);
+        }
+    }
+}
+
+#[test]
+fn mov_reduction_works(){
+    let ell = supersingular_curve();
+    for _i in 1..5{
+        let p = sample_order_n_point(&ell);
+        let m = Integer::sample_uniform(&Integer::from(0), &Integer::from(N));
+        let q = ell.scalar_mult(m.clone(), p.clone());
+
+        let (alpha, beta) = ell.mov_reduction(&p, &q, Integer::from(N)).unwrap();
+        assert!(alpha != K2::from_int(1));
+        assert_eq!(K2::exp(alpha.clone(), Integer::from(N)), K2::from_int(1));
+        assert_eq!(K2::exp(alpha, m), beta);
+    }
+}
+
+#[test]
+fn mov_reduction_general_weierstrass(){
+    // y -> y + x + 2 sends y^2 = x^3 + x to a curve with non zero a_1 and a_3
+    let (s, t) = (K::from_int(1), K::from_int(2));
+    let ell = EllipticCurve{
+        a_1: K::from_int(2)*s.clone(),
+        a_3: K::from_int(2)*t.clone(),
+
+        a_2: -s.clone()*s.clone(),
+        a_4: K::from_int(1) - K::from_int(2)*s.clone()*t.clone(),
+        a_6: -t.clone()*t.clone(),
+    };
+    let to_ell = |p : ProjKPoint<K>| match p{
+        ProjKPoint::InfPoint => ProjKPoint::InfPoint,
+        ProjKPoint::FinPoint(x, y) => ProjKPoint::FinPoint(x.clone(), y - s.clone()*x - t.clone()),
+    };
+    let p = to_ell(sample_order_n_point(&supersingular_curve()));
+    assert!(ell.is_on_curve(&p));
+    let m = Integer::sample_uniform(&Integer::from(0), &Integer::from(N));
+    let q = ell.scalar_mult(m.clone(), p.clone());
+
+    let (alpha, beta) = ell.mov_reduction(&p, &q, Integer::from(N)).unwrap();
+    assert_eq!(K2::exp(alpha, m), beta);
+}
+
+#[test]
+fn mov_reduction_unsupported_degree(){
+    // p = 4 mod 7 has order 3 modulo 7
+    let ell = supersingular_curve();
+    let p = ell.sample_point();
+    assert_eq!(ell.mov_reduction(&p, &p, Integer::from(7)), Err(Error::UnsupportedEmbeddingDegree));
+}
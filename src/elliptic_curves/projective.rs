@@ -0,0 +1,384 @@
+use super::*;
+
+use crate::field::map_with_inverses;
+
+// Jacobian coordinates (X : Y : Z) of the affine point (X/Z^2, Y/Z^3), the infinite point has Z = 0
+#[derive(Debug, Clone)]
+pub struct JacobianPoint<K>{
+    pub x : K,
+    pub y : K,
+    pub z : K,
+}
+
+// Homogeneous projective coordinates (X : Y : Z) of the affine point (X/Z, Y/Z), the infinite point is (0 : 1 : 0)
+#[derive(Debug, Clone)]
+pub struct HomogeneousPoint<K>{
+    pub x : K,
+    pub y : K,
+    pub z : K,
+}
+
+impl<K : Field> JacobianPoint<K>{
+    pub fn infinite_point() -> JacobianPoint<K>{
+        JacobianPoint{
+            x: K::from_int(1),
+            y: K::from_int(1),
+            z: K::from_int(0),
+        }
+    }
+
+    pub fn is_infinite(&self) -> bool{
+        self.z == K::from_int(0)
+    }
+
+    pub fn from_affine(point : ProjKPoint<K>) -> JacobianPoint<K>{
+        match point{
+            ProjKPoint::InfPoint => Self::infinite_point(),
+            ProjKPoint::FinPoint(x, y) => JacobianPoint{ x, y, z: K::from_int(1) },
+        }
+    }
+
+    pub fn to_affine(&self) -> ProjKPoint<K>{
+        if self.is_infinite(){
+            return ProjKPoint::InfPoint;
+        }
+        let z_inv = K::from_int(1)/self.z.clone();
+        self.to_affine_with(z_inv)
+    }
+
+    fn to_affine_with(&self, z_inv : K) -> ProjKPoint<K>{
+        let z_inv2 = z_inv.clone()*z_inv.clone();
+        ProjKPoint::FinPoint(self.x.clone()*z_inv2.clone(), self.y.clone()*z_inv2*z_inv)
+    }
+
+    // Affine points of all the points with a single inversion
+    pub fn batch_to_affine(points : &[JacobianPoint<K>]) -> Vec<ProjKPoint<K>>{
+        map_with_inverses(points, |point| point.z.clone(), |point, z_inv| {
+            if point.is_infinite(){
+                ProjKPoint::InfPoint
+            }else{
+                point.to_affine_with(z_inv)
+            }
+        })
+    }
+}
+
+impl<K : Field> PartialEq for JacobianPoint<K>{
+    fn eq(&self, other : &JacobianPoint<K>) -> bool{
+        if self.is_infinite() || other.is_infinite(){
+            return self.is_infinite() && other.is_infinite();
+        }
+        let z1z1 = self.z.clone()*self.z.clone();
+        let z2z2 = other.z.clone()*other.z.clone();
+        self.x.clone()*z2z2.clone() == other.x.clone()*z1z1.clone() &&
+        self.y.clone()*z2z2*other.z.clone() == other.y.clone()*z1z1*self.z.clone()
+    }
+}
+
+impl<K : Field> HomogeneousPoint<K>{
+    pub fn infinite_point() -> HomogeneousPoint<K>{
+        HomogeneousPoint{
+            x: K::from_int(0),
+            y: K::from_int(1),
+            z: K::from_int(0),
+        }
+    }
+
+    pub fn is_infinite(&self) -> bool{
+        self.z == K::from_int(0)
+    }
+
+    pub fn from_affine(point : ProjKPoint<K>) -> HomogeneousPoint<K>{
+        match point{
+            ProjKPoint::InfPoint => Self::infinite_point(),
+            ProjKPoint::FinPoint(x, y) => HomogeneousPoint{ x, y, z: K::from_int(1) },
+        }
+    }
+
+    pub fn to_affine(&self) -> ProjKPoint<K>{
+        if self.is_infinite(){
+            return ProjKPoint::InfPoint;
+        }
+        let z_inv = K::from_int(1)/self.z.clone();
+        ProjKPoint::FinPoint(self.x.clone()*z_inv.clone(), self.y.clone()*z_inv)
+    }
+
+    // Affine points of all the points with a single inversion
+    pub fn batch_to_affine(points : &[HomogeneousPoint<K>]) -> Vec<ProjKPoint<K>>{
+        map_with_inverses(points, |point| point.z.clone(), |point, z_inv| {
+            if point.is_infinite(){
+                ProjKPoint::InfPoint
+            }else{
+                ProjKPoint::FinPoint(point.x.clone()*z_inv.clone(), point.y.clone()*z_inv)
+            }
+        })
+    }
+}
+
+impl<K : Field> PartialEq for HomogeneousPoint<K>{
+    fn eq(&self, other : &HomogeneousPoint<K>) -> bool{
+        if self.is_infinite() || other.is_infinite(){
+            return self.is_infinite() && other.is_infinite();
+        }
+        self.x.clone()*other.z.clone() == other.x.clone()*self.z.clone() &&
+        self.y.clone()*other.z.clone() == other.y.clone()*self.z.clone()
+    }
+}
+
+impl<K> fmt::Display for JacobianPoint<K>
+    where K : fmt::Display{
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "[{} : {} : {}]_J", self.x, self.y, self.z)
+        }
+}
+
+impl<K> fmt::Display for HomogeneousPoint<K>
+    where K : fmt::Display{
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "[{} : {} : {}]", self.x, self.y, self.z)
+        }
+}
+
+// Inversion-free formulas of the Explicit-Formulas Database for y^2 = x^3 + a*x + b,
+// with the specializations for a = -3 chosen at run time
+impl<K> EllipticCurve<K>
+    where K : Field{
+
+        fn assert_reduced_weierstrass(&self){
+            if !self.is_reduced_weierstrass(){
+                panic!("projective coordinates must be used only with curves in reduced form");
+            }
+        }
+
+        fn a_is_minus_three(&self) -> bool{
+            self.a_4 == K::from_int(-3)
+        }
+
+        // dbl-2007-bl, or dbl-2001-b when a = -3
+        pub fn jacobian_double(&self, p : JacobianPoint<K>) -> JacobianPoint<K>{
+            self.assert_reduced_weierstrass();
+            if p.is_infinite() || p.y == K::from_int(0){
+                return JacobianPoint::infinite_point();
+            }
+
+            if self.a_is_minus_three(){
+                let delta = p.z.clone()*p.z.clone();
+                let gamma = p.y.clone()*p.y.clone();
+                let beta = p.x.clone()*gamma.clone();
+                let alpha = K::from_int(3)*(p.x.clone() - delta.clone())*(p.x + delta.clone());
+                let x3 = alpha.clone()*alpha.clone() - K::from_int(8)*beta.clone();
+                let z3 = (p.y.clone() + p.z.clone())*(p.y + p.z) - gamma.clone() - delta;
+                let y3 = alpha*(K::from_int(4)*beta - x3.clone()) - K::from_int(8)*gamma.clone()*gamma;
+                return JacobianPoint{ x: x3, y: y3, z: z3 };
+            }
+
+            let xx = p.x.clone()*p.x.clone();
+            let yy = p.y.clone()*p.y.clone();
+            let yyyy = yy.clone()*yy.clone();
+            let zz = p.z.clone()*p.z.clone();
+            let s = K::from_int(2)*((p.x.clone() + yy.clone())*(p.x + yy.clone()) - xx.clone() - yyyy.clone());
+            let m = K::from_int(3)*xx + self.a_4.clone()*zz.clone()*zz.clone();
+            let t = m.clone()*m.clone() - K::from_int(2)*s.clone();
+            JacobianPoint{
+                x: t.clone(),
+                y: m*(s - t) - K::from_int(8)*yyyy,
+                z: (p.y.clone() + p.z.clone())*(p.y + p.z) - yy - zz,
+            }
+        }
+
+        // add-2007-bl
+        pub fn jacobian_add(&self, p : JacobianPoint<K>, q : JacobianPoint<K>) -> JacobianPoint<K>{
+            self.assert_reduced_weierstrass();
+            if p.is_infinite(){
+                return q;
+            }
+            if q.is_infinite(){
+                return p;
+            }
+
+            let z1z1 = p.z.clone()*p.z.clone();
+            let z2z2 = q.z.clone()*q.z.clone();
+            let u1 = p.x.clone()*z2z2.clone();
+            let u2 = q.x.clone()*z1z1.clone();
+            let s1 = p.y.clone()*q.z.clone()*z2z2.clone();
+            let s2 = q.y.clone()*p.z.clone()*z1z1.clone();
+            let h = u2 - u1.clone();
+            let r = K::from_int(2)*(s2 - s1.clone());
+            if h == K::from_int(0){
+                return if r == K::from_int(0){
+                    self.jacobian_double(p)
+                }else{
+                    JacobianPoint::infinite_point()
+                };
+            }
+
+            let i = K::from_int(4)*h.clone()*h.clone();
+            let j = h.clone()*i.clone();
+            let v = u1*i;
+            let x3 = r.clone()*r.clone() - j.clone() - K::from_int(2)*v.clone();
+            JacobianPoint{
+                y: r*(v - x3.clone()) - K::from_int(2)*s1*j,
+                x: x3,
+                z: ((p.z.clone() + q.z.clone())*(p.z + q.z) - z1z1 - z2z2)*h,
+            }
+        }
+
+        // Mixed addition madd-2007-bl, with q given in affine coordinates
+        pub fn jacobian_add_affine(&self, p : JacobianPoint<K>, q : &ProjKPoint<K>) -> JacobianPoint<K>{
+            self.assert_reduced_weierstrass();
+            let (x2, y2) = match q{
+                ProjKPoint::InfPoint => return p,
+                ProjKPoint::FinPoint(x2, y2) => (x2.clone(), y2.clone()),
+            };
+            if p.is_infinite(){
+                return JacobianPoint::from_affine(q.clone());
+            }
+
+            let z1z1 = p.z.clone()*p.z.clone();
+            let u2 = x2*z1z1.clone();
+            let s2 = y2*p.z.clone()*z1z1.clone();
+            let h = u2 - p.x.clone();
+            let r = K::from_int(2)*(s2 - p.y.clone());
+            if h == K::from_int(0){
+                return if r == K::from_int(0){
+                    self.jacobian_double(p)
+                }else{
+                    JacobianPoint::infinite_point()
+                };
+            }
+
+            let hh = h.clone()*h.clone();
+            let i = K::from_int(4)*hh.clone();
+            let j = h.clone()*i.clone();
+            let v = p.x*i;
+            let x3 = r.clone()*r.clone() - j.clone() - K::from_int(2)*v.clone();
+            JacobianPoint{
+                y: r*(v - x3.clone()) - K::from_int(2)*p.y*j,
+                x: x3,
+                z: (p.z.clone() + h.clone())*(p.z + h) - z1z1 - hh,
+            }
+        }
+
+        // dbl-2007-bl, with w = 3(X-Z)(X+Z) when a = -3
+        pub fn homogeneous_double(&self, p : HomogeneousPoint<K>) -> HomogeneousPoint<K>{
+            self.assert_reduced_weierstrass();
+            if p.is_infinite() || p.y == K::from_int(0){
+                return HomogeneousPoint::infinite_point();
+            }
+
+            let xx = p.x.clone()*p.x.clone();
+            let w = if self.a_is_minus_three(){
+                K::from_int(3)*(p.x.clone() - p.z.clone())*(p.x.clone() + p.z.clone())
+            }else{
+                self.a_4.clone()*p.z.clone()*p.z.clone() + K::from_int(3)*xx.clone()
+            };
+            let s = K::from_int(2)*p.y.clone()*p.z;
+            let ss = s.clone()*s.clone();
+            let r = p.y*s.clone();
+            let rr = r.clone()*r.clone();
+            let b = (p.x.clone() + r.clone())*(p.x + r) - xx - rr.clone();
+            let h = w.clone()*w.clone() - K::from_int(2)*b.clone();
+            HomogeneousPoint{
+                x: h.clone()*s.clone(),
+                y: w*(b - h) - K::from_int(2)*rr,
+                z: s*ss,
+            }
+        }
+
+        // add-1998-cmo-2
+        pub fn homogeneous_add(&self, p : HomogeneousPoint<K>, q : HomogeneousPoint<K>) -> HomogeneousPoint<K>{
+            self.assert_reduced_weierstrass();
+            if p.is_infinite(){
+                return q;
+            }
+            if q.is_infinite(){
+                return p;
+            }
+
+            let y1z2 = p.y.clone()*q.z.clone();
+            let x1z2 = p.x.clone()*q.z.clone();
+            let z1z2 = p.z.clone()*q.z.clone();
+            let u = q.y.clone()*p.z.clone() - y1z2.clone();
+            let v = q.x.clone()*p.z.clone() - x1z2.clone();
+            if v == K::from_int(0){
+                return if u == K::from_int(0){
+                    self.homogeneous_double(p)
+                }else{
+                    HomogeneousPoint::infinite_point()
+                };
+            }
+
+            let uu = u.clone()*u.clone();
+            let vv = v.clone()*v.clone();
+            let vvv = v.clone()*vv.clone();
+            let r = vv*x1z2;
+            let a = uu*z1z2.clone() - vvv.clone() - K::from_int(2)*r.clone();
+            HomogeneousPoint{
+                x: v*a.clone(),
+                y: u*(r - a) - vvv.clone()*y1z2,
+                z: vvv*z1z2,
+            }
+        }
+
+        // Mixed addition madd-1998-cmo, with q given in affine coordinates
+        pub fn homogeneous_add_affine(&self, p : HomogeneousPoint<K>, q : &ProjKPoint<K>) -> HomogeneousPoint<K>{
+            self.assert_reduced_weierstrass();
+            let (x2, y2) = match q{
+                ProjKPoint::InfPoint => return p,
+                ProjKPoint::FinPoint(x2, y2) => (x2.clone(), y2.clone()),
+            };
+            if p.is_infinite(){
+                return HomogeneousPoint::from_affine(q.clone());
+            }
+
+            let u = y2*p.z.clone() - p.y.clone();
+            let v = x2*p.z.clone() - p.x.clone();
+            if v == K::from_int(0){
+                return if u == K::from_int(0){
+                    self.homogeneous_double(p)
+                }else{
+                    HomogeneousPoint::infinite_point()
+                };
+            }
+
+            let uu = u.clone()*u.clone();
+            let vv = v.clone()*v.clone();
+            let vvv = v.clone()*vv.clone();
+            let r = vv*p.x;
+            let a = uu*p.z.clone() - vvv.clone() - K::from_int(2)*r.clone();
+            HomogeneousPoint{
+                x: v*a.clone(),
+                y: u*(r - a) - vvv.clone()*p.y,
+                z: vvv*p.z,
+            }
+        }
+
+        // Double-and-add with Jacobian doublings and mixed additions, a single inversion being left to to_affine
+        pub fn scalar_mult_jacobian(&self, n : K::Integer, point : ProjKPoint<K>) -> JacobianPoint<K>{
+            assert!(self.is_on_curve(&point));
+            if n < K::Integer::from(0){
+                return self.scalar_mult_jacobian(-n, self.neg_point(point));
+            }
+
+            let mut logm = 0;
+            let mut m = n.clone();
+            while m != K::Integer::from(0){
+                m >>= 1;
+                logm += 1;
+            }
+
+            let mut result = JacobianPoint::infinite_point();
+            while logm >= 1{
+                let bit = (n.clone()&(K::Integer::from(1)<<(logm-1)))>>(logm-1); // the current bit
+                logm -= 1;
+                result = self.jacobian_double(result);
+                if bit != K::Integer::from(0){
+                    result = self.jacobian_add_affine(result, &point);
+                }
+            }
+            result
+        }
+}
+
+#[cfg(all(test, feature = "gmp"))]
+mod test;
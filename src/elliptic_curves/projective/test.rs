@@ -0,0 +1,116 @@
+use super::*;
+
+use crate::field::*;
+use crate::finite_fields::*;
+
+pub type Integer = gmp::mpz::Mpz;
+
+const P : u32 = 10169;
+
+declare_finite_field!(K, Integer, Integer::from(P), m10169);
+
+fn sample_element() -> K{
+    K::new(Integer::sample_uniform(&Integer::from(0), &Integer::from(P)))
+}
+
+fn sample_nonzero() -> K{
+    K::new(Integer::sample_uniform(&Integer::from(1), &Integer::from(P)))
+}
+
+// Reduced Weierstrass curve, with a = -3 when minus_three is set
+fn sample_curve(minus_three : bool) -> EllipticCurve<K>{
+    loop{
+        let a = if minus_three { K::from_int(-3) } else { sample_element() };
+        let ell = EllipticCurve::new_reduced_weierstrass(a, sample_element());
+        if ell.discriminant() != K::from_int(0){
+            return ell;
+        }
+    }
+}
+
+// A random point, or one of the special cases of the affine addition with p
+fn sample_other_point(ell : &EllipticCurve<K>, p : &ProjKPoint<K>, i : u32) -> ProjKPoint<K>{
+    match i%5{
+        0 => p.clone(),
+        1 => ell.neg_point(p.clone()),
+        2 => ProjKPoint::InfPoint,
+        _ => ell.sample_point(),
+    }
+}
+
+#[test]
+fn jacobian_coincide_with_affine(){
+    for j in 0..10{
+        let ell = sample_curve(j%2 == 0);
+        for i in 0..50{
+            let p = ell.sample_point();
+            let q = sample_other_point(&ell, &p, i);
+            // non normalized coordinates of q
+            let z = sample_nonzero();
+            let q_jac = match JacobianPoint::from_affine(q.clone()){
+                q_jac if q_jac.is_infinite() => q_jac,
+                q_jac => JacobianPoint{ x: q_jac.x*z.clone()*z.clone(), y: q_jac.y*z.clone()*z.clone()*z.clone(), z },
+            };
+            let p_jac = JacobianPoint::from_affine(p.clone());
+
+            assert_eq!(q_jac.to_affine(), q);
+            assert_eq!(ell.jacobian_add(p_jac.clone(), q_jac.clone()).to_affine(), ell.add_points(p.clone(), q.clone()));
+            assert_eq!(ell.jacobian_add(q_jac.clone(), p_jac.clone()).to_affine(), ell.add_points(p.clone(), q.clone()));
+            assert_eq!(ell.jacobian_add_affine(q_jac.clone(), &p).to_affine(), ell.add_points(p.clone(), q.clone()));
+            assert_eq!(ell.jacobian_double(q_jac).to_affine(), ell.add_points(q.clone(), q));
+        }
+    }
+}
+
+#[test]
+fn homogeneous_coincide_with_affine(){
+    for j in 0..10{
+        let ell = sample_curve(j%2 == 0);
+        for i in 0..50{
+            let p = ell.sample_point();
+            let q = sample_other_point(&ell, &p, i);
+            let z = sample_nonzero();
+            let q_hom = match HomogeneousPoint::from_affine(q.clone()){
+                q_hom if q_hom.is_infinite() => q_hom,
+                q_hom => HomogeneousPoint{ x: q_hom.x*z.clone(), y: q_hom.y*z.clone(), z },
+            };
+            let p_hom = HomogeneousPoint::from_affine(p.clone());
+
+            assert_eq!(q_hom.to_affine(), q);
+            assert_eq!(ell.homogeneous_add(p_hom.clone(), q_hom.clone()).to_affine(), ell.add_points(p.clone(), q.clone()));
+            assert_eq!(ell.homogeneous_add(q_hom.clone(), p_hom.clone()).to_affine(), ell.add_points(p.clone(), q.clone()));
+            assert_eq!(ell.homogeneous_add_affine(q_hom.clone(), &p).to_affine(), ell.add_points(p.clone(), q.clone()));
+            assert_eq!(ell.homogeneous_double(q_hom).to_affine(), ell.add_points(q.clone(), q));
+        }
+    }
+}
+
+#[test]
+fn scalar_mult_jacobian_coincide(){
+    for j in 0..10{
+        let ell = sample_curve(j%2 == 0);
+        for _i in 0..20{
+            let p = ell.sample_point();
+            let n = Integer::sample_uniform(&Integer::from(-2*P as i64), &Integer::from(2*P));
+            assert_eq!(ell.scalar_mult_jacobian(n.clone(), p.clone()).to_affine(), ell.scalar_mult(n, p));
+        }
+    }
+}
+
+#[test]
+fn batch_to_affine_coincide(){
+    let ell = sample_curve(false);
+    let mut jacobian = Vec::new();
+    let mut homogeneous = Vec::new();
+    for i in 0..30{
+        let p = if i%10 == 0 { ProjKPoint::InfPoint } else { ell.sample_point() };
+        let n = Integer::from(2 + i);
+        jacobian.push(ell.scalar_mult_jacobian(n, p.clone()));
+        let q = HomogeneousPoint::from_affine(p);
+        homogeneous.push(ell.homogeneous_double(q));
+    }
+    let expected : Vec<ProjKPoint<K>> = jacobian.iter().map(|p| p.to_affine()).collect();
+    assert_eq!(JacobianPoint::batch_to_affine(&jacobian), expected);
+    let expected : Vec<ProjKPoint<K>> = homogeneous.iter().map(|p| p.to_affine()).collect();
+    assert_eq!(HomogeneousPoint::batch_to_affine(&homogeneous), expected);
+}
@@ -8,6 +8,7 @@ pub type Integer = gmp::mpz::Mpz;
 const P : u32 = 10169;
 
 declare_finite_field!(K, Integer, Integer::from(P), m10169);
+declare_finite_field!(Z1050589, Integer, Integer::from(1019*1031), m1050589); // not a field
 
 fn sample_point(ell : &EllipticCurve<K>) -> ProjKPoint<K>{
     if ell.is_reduced_weierstrass(){
@@ -75,6 +76,23 @@ fn reduced_weierstrass_work() {
 }
 
 
+#[test]
+fn j_invariant_special_values(){
+    // y^2 = x^3 + x and y^2 = x^3 + 1, and y^2 + y = x^3 of discriminant -27
+    assert_eq!(EllipticCurve::new_reduced_weierstrass(K::from_int(1), K::from_int(0)).j_invariant(), K::from_int(1728));
+    assert_eq!(EllipticCurve::new_reduced_weierstrass(K::from_int(0), K::from_int(1)).j_invariant(), K::from_int(0));
+    let ell = EllipticCurve{
+        a_1: K::from_int(0),
+        a_3: K::from_int(1),
+
+        a_2: K::from_int(0),
+        a_4: K::from_int(0),
+        a_6: K::from_int(0),
+    };
+    assert_eq!(ell.discriminant(), K::from_int(-27));
+    assert_eq!(ell.to_reduced_weierstrass().j_invariant(), K::from_int(0));
+}
+
 #[test]
 fn j_invariant_constant_weierstrass() {
     for _i in 1..10{
@@ -166,4 +184,48 @@ fn velu_formula_match_isogeny(){
             assert!(ell_velu.is_on_curve(&ell.velu_projection(&p, q)));
         }
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn checked_operations_report_errors(){
+    let ell = sample_elliptic_curve();
+    let off_curve = (0..).map(|x| ProjKPoint::FinPoint(K::from_int(x), K::from_int(0)))
+                         .find(|point| !ell.is_on_curve(point)).unwrap();
+    let p = sample_point(&ell);
+    assert_eq!(ell.checked_add_points(p.clone(), off_curve.clone()), Err(crate::error::Error::NotOnCurve));
+    assert_eq!(ell.checked_add_points(p.clone(), p.clone()), Ok(ell.add_points(p.clone(), p.clone())));
+
+    if !ell.is_reduced_weierstrass(){
+        assert_eq!(ell.try_velu_curve(&p), Err(crate::error::Error::NotReducedWeierstrass));
+    }
+    let ell = ell.to_reduced_weierstrass();
+    assert_eq!(ell.try_velu_curve(&off_curve), Err(crate::error::Error::NotOnCurve));
+    let p = sample_point(&ell);
+    assert_eq!(ell.try_velu_curve(&p), Ok(ell.velu_curve(&p)));
+}
+
+#[test]
+fn checked_addition_finds_factors(){
+    // Lenstra's method: the multiples of a point modulo 1019*1031 reach the infinity modulo one factor first
+    let n = Integer::from(1019*1031);
+    'curves: loop{
+        let (x, y, a) = (Z1050589::new(Integer::sample_uniform(&Integer::from(0), &n)),
+                         Z1050589::new(Integer::sample_uniform(&Integer::from(0), &n)),
+                         Z1050589::new(Integer::sample_uniform(&Integer::from(0), &n)));
+        let b = y.clone()*y.clone() - x.clone()*x.clone()*x.clone() - a.clone()*x.clone();
+        let ell = EllipticCurve::new_reduced_weierstrass(a, b);
+        let p = ProjKPoint::FinPoint(x, y);
+        let mut q = p.clone();
+        for _i in 0..1100{
+            match ell.checked_add_points(q, p.clone()){
+                Ok(ProjKPoint::InfPoint) => continue 'curves, // same order modulo both factors
+                Ok(sum) => q = sum,
+                Err(error) => {
+                    assert_eq!(error, crate::error::Error::NotInvertible);
+                    return;
+                }
+            }
+        }
+        panic!("the order modulo 1019 is at most 1084");
+    }
+}
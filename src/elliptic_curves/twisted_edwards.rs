@@ -0,0 +1,220 @@
+use crate::field::map_with_inverses;
+use crate::finite_fields::FiniteField;
+
+use super::*;
+
+// a*x^2 + y^2 = 1 + d*x^2*y^2
+#[derive(Debug, PartialEq, Clone)]
+pub struct TwistedEdwardsCurve<K>{
+    pub a : K,
+    pub d : K,
+}
+
+// Extended coordinates (X : Y : Z : T) of the affine point (X/Z, Y/Z), with X*Y = T*Z
+#[derive(Debug, Clone)]
+pub struct ExtendedPoint<K>{
+    pub x : K,
+    pub y : K,
+    pub z : K,
+    pub t : K,
+}
+
+impl<K> PartialEq for ExtendedPoint<K> where K : Field{
+    fn eq(&self, other : &ExtendedPoint<K>) -> bool{
+        self.x.clone()*other.z.clone() == other.x.clone()*self.z.clone() &&
+        self.y.clone()*other.z.clone() == other.y.clone()*self.z.clone()
+    }
+}
+
+impl<K> fmt::Display for ExtendedPoint<K>
+    where K : fmt::Display{
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "[{} : {} : {} : {}]", self.x, self.y, self.z, self.t)
+        }
+}
+
+impl<K : Field> ExtendedPoint<K>{
+    pub fn from_affine(x : K, y : K) -> ExtendedPoint<K>{
+        ExtendedPoint{
+            t: x.clone()*y.clone(),
+            x,
+            y,
+            z: K::from_int(1),
+        }
+    }
+
+    pub fn to_affine(&self) -> (K, K){
+        (self.x.clone()/self.z.clone(), self.y.clone()/self.z.clone())
+    }
+
+    // Affine coordinates of all the points with a single inversion
+    pub fn batch_to_affine(points : &[ExtendedPoint<K>]) -> Vec<(K, K)>{
+        map_with_inverses(points, |point| point.z.clone(), |point, z_inv| (point.x.clone()*z_inv.clone(), point.y.clone()*z_inv))
+    }
+
+    // The point (0, 1)
+    pub fn neutral_point() -> ExtendedPoint<K>{
+        Self::from_affine(K::from_int(0), K::from_int(1))
+    }
+}
+
+impl<K> Display for TwistedEdwardsCurve<K> where K : Field + Display{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}*x^2 + y^2 = 1 + {}*x^2*y^2", self.a, self.d)
+    }
+}
+
+impl<K> TwistedEdwardsCurve<K>
+    where K : Field{
+        pub fn new(a : K, d : K) -> TwistedEdwardsCurve<K>{
+            assert!(a != K::from_int(0) && d != K::from_int(0) && a != d, "singular twisted Edwards curve");
+            TwistedEdwardsCurve{ a, d }
+        }
+
+        pub fn is_on_curve(&self, point : &ExtendedPoint<K>) -> bool{
+            let ExtendedPoint{ x, y, z, t } = point;
+            let (x2, y2, z2) = (x.clone()*x.clone(), y.clone()*y.clone(), z.clone()*z.clone());
+            // (a*X^2 + Y^2)*Z^2 = Z^4 + d*X^2*Y^2
+            z != &K::from_int(0) && x.clone()*y.clone() == t.clone()*z.clone() &&
+            (self.a.clone()*x2.clone() + y2.clone())*z2.clone() == z2.clone()*z2 + self.d.clone()*x2*y2
+        }
+
+        pub fn neg_point(&self, point : ExtendedPoint<K>) -> ExtendedPoint<K>{
+            ExtendedPoint{
+                x: -point.x,
+                y: point.y,
+                z: point.z,
+                t: -point.t,
+            }
+        }
+
+        // Unified addition (Hisil-Wong-Carter-Dawson), complete when a is a square and d is not
+        pub fn add_points(&self, point1 : ExtendedPoint<K>, point2 : ExtendedPoint<K>) -> ExtendedPoint<K>{
+            assert!(self.is_on_curve(&point1));
+            assert!(self.is_on_curve(&point2));
+
+            let a = point1.x.clone()*point2.x.clone();
+            let b = point1.y.clone()*point2.y.clone();
+            let c = self.d.clone()*point1.t*point2.t;
+            let d = point1.z*point2.z;
+            let e = (point1.x + point1.y)*(point2.x + point2.y) - a.clone() - b.clone();
+            let f = d.clone() - c.clone();
+            let g = d + c;
+            let h = b - self.a.clone()*a;
+            ExtendedPoint{
+                x: e.clone()*f.clone(),
+                y: g.clone()*h.clone(),
+                z: f*g,
+                t: e*h,
+            }
+        }
+
+        pub fn double_point(&self, point : ExtendedPoint<K>) -> ExtendedPoint<K>{
+            assert!(self.is_on_curve(&point));
+
+            let a = point.x.clone()*point.x.clone();
+            let b = point.y.clone()*point.y.clone();
+            let c = K::from_int(2)*point.z.clone()*point.z;
+            let d = self.a.clone()*a.clone();
+            let e = (point.x.clone() + point.y.clone())*(point.x + point.y) - a - b.clone();
+            let g = d.clone() + b.clone();
+            let f = g.clone() - c;
+            let h = d - b;
+            ExtendedPoint{
+                x: e.clone()*f.clone(),
+                y: g.clone()*h.clone(),
+                z: f*g,
+                t: e*h,
+            }
+        }
+
+        pub fn scalar_mult(&self, n : K::Integer, point : ExtendedPoint<K>) -> ExtendedPoint<K>{
+            assert!(self.is_on_curve(&point));
+            if n < K::Integer::from(0){
+                return self.scalar_mult(-n, self.neg_point(point));
+            }
+
+            let mut logm = 0;
+            let mut m = n.clone();
+            while m != K::Integer::from(0){
+                m >>= 1;
+                logm += 1;
+            }
+
+            let mut result = ExtendedPoint::neutral_point();
+            while logm >= 1{
+                let bit = (n.clone()&(K::Integer::from(1)<<(logm-1)))>>(logm-1); // the current bit
+                logm -= 1;
+                result = self.double_point(result);
+                if bit != K::Integer::from(0){
+                    result = self.add_points(result, point.clone());
+                }
+            }
+            result
+        }
+}
+
+impl<K> TwistedEdwardsCurve<K>
+    where K : FiniteField + Display{
+
+        // The curve is birational to B*v^2 = u^3 + A*u^2 + u with A = 2(a+d)/(a-d) and B = 4/(a-d).
+        // Return s with s^2 = B, so that (u, v) -> (u, s*v) lands on a curve with B = 1
+        fn montgomery_scaling(&self) -> K{
+            let b = K::from_int(4)/(self.a.clone() - self.d.clone());
+            if b.legendre_symbol() == -1{
+                panic!("the curve is only birational to a quadratic twist of a Montgomery curve y^2 = x^3 + A*x^2 + x");
+            }
+            b.square_root()
+        }
+
+        pub fn to_montgomery(&self) -> EllipticCurve<K>{
+            EllipticCurve::new_montgomery(K::from_int(2)*(self.a.clone() + self.d.clone())/(self.a.clone() - self.d.clone()))
+        }
+
+        // (x, y) -> (u, v) = ((1+y)/(1-y), s*u/x), sending (0, 1) to the infinite point and (0, -1) to (0, 0)
+        pub fn to_montgomery_point(&self, point : &ExtendedPoint<K>) -> ProjKPoint<K>{
+            assert!(self.is_on_curve(point));
+            let (x, y) = point.to_affine();
+            if x == K::from_int(0){
+                return if y == K::from_int(1){
+                    ProjKPoint::InfPoint
+                }else{
+                    ProjKPoint::FinPoint(K::from_int(0), K::from_int(0))
+                };
+            }
+            let u = (K::from_int(1) + y.clone())/(K::from_int(1) - y);
+            let v = self.montgomery_scaling()*u.clone()/x;
+            ProjKPoint::FinPoint(u, v)
+        }
+
+        // (u, v) -> (x, y) = (s*u/v, (u-1)/(u+1)), defined except at the points with v = 0 or u = -1 other than (0, 0)
+        pub fn from_montgomery_point(&self, point : &ProjKPoint<K>) -> ExtendedPoint<K>{
+            match point{
+                ProjKPoint::InfPoint => ExtendedPoint::neutral_point(),
+                ProjKPoint::FinPoint(u, v) => {
+                    if u == &K::from_int(0) && v == &K::from_int(0){
+                        return ExtendedPoint::from_affine(K::from_int(0), K::from_int(-1));
+                    }
+                    if v == &K::from_int(0) || u == &K::from_int(-1){
+                        panic!("exceptional point of the birational map to the twisted Edwards curve");
+                    }
+                    ExtendedPoint::from_affine(self.montgomery_scaling()*u.clone()/v.clone(),
+                                               (u.clone() - K::from_int(1))/(u.clone() + K::from_int(1)))
+                }
+            }
+        }
+}
+
+impl<K> EllipticCurve<K>
+    where K : FiniteField + Display{
+        // y^2 = x^3 + A*x^2 + x is birational to (A+2)*x^2 + y^2 = 1 + (A-2)*x^2*y^2
+        pub fn to_twisted_edwards(&self) -> TwistedEdwardsCurve<K>{
+            if !self.is_montgomery(){
+                panic!("to_twisted_edwards must be used with Montgomery curves");
+            }
+            TwistedEdwardsCurve::new(self.a_2.clone() + K::from_int(2), self.a_2.clone() - K::from_int(2))
+        }
+}
+
+#[cfg(all(test, feature = "gmp"))]
+mod test;
@@ -0,0 +1,113 @@
+use super::*;
+
+use crate::field::*;
+use crate::finite_fields::*;
+
+pub type Integer = gmp::mpz::Mpz;
+
+const P : u32 = 10169;
+
+declare_finite_field!(K, Integer, Integer::from(P), m10169);
+
+fn sample_element() -> K{
+    K::new(Integer::sample_uniform(&Integer::from(0), &Integer::from(P)))
+}
+
+// Complete twisted Edwards curve (a square and d non square) birational to a Montgomery curve y^2 = x^3 + A*x^2 + x
+fn sample_edwards_curve() -> TwistedEdwardsCurve<K>{
+    loop{
+        let (a, d) = (sample_element(), sample_element());
+        if a.legendre_symbol() == 1 && d.legendre_symbol() == -1 && (K::from_int(4)/(a.clone()-d.clone())).legendre_symbol() == 1{
+            return TwistedEdwardsCurve::new(a, d);
+        }
+    }
+}
+
+// Montgomery point outside the exceptional set of the birational map
+fn sample_point(ell : &EllipticCurve<K>) -> ProjKPoint<K>{
+    loop{
+        if let ProjKPoint::FinPoint(u, v) = ell.sample_point(){
+            if v != K::from_int(0) && u != K::from_int(-1){
+                return ProjKPoint::FinPoint(u, v);
+            }
+        }
+    }
+}
+
+#[test]
+fn birational_maps_are_inverse(){
+    for _j in 0..10{
+        let edwards = sample_edwards_curve();
+        let montgomery = edwards.to_montgomery();
+        for _i in 0..20{
+            let p = sample_point(&montgomery);
+            let q = edwards.from_montgomery_point(&p);
+            assert!(edwards.is_on_curve(&q));
+            assert_eq!(edwards.to_montgomery_point(&q), p);
+        }
+        assert_eq!(edwards.to_montgomery_point(&ExtendedPoint::neutral_point()), ProjKPoint::InfPoint);
+        assert!(montgomery.is_on_curve(&edwards.to_montgomery_point(&ExtendedPoint::from_affine(K::from_int(0), K::from_int(-1)))));
+    }
+}
+
+// Montgomery curve whose twisted Edwards form is complete, with that form
+fn sample_montgomery_curve() -> (EllipticCurve<K>, TwistedEdwardsCurve<K>){
+    loop{
+        let montgomery = EllipticCurve::new_montgomery(sample_element());
+        if montgomery.discriminant() == K::from_int(0){
+            continue;
+        }
+        let edwards = montgomery.to_twisted_edwards();
+        if edwards.a.legendre_symbol() == 1 && edwards.d.legendre_symbol() == -1{
+            return (montgomery, edwards);
+        }
+    }
+}
+
+#[test]
+fn addition_coincide_with_montgomery(){
+    for _j in 0..10{
+        let (montgomery, edwards) = sample_montgomery_curve();
+        assert_eq!(edwards.to_montgomery(), montgomery);
+        for _i in 0..20{
+            let p = sample_point(&montgomery);
+            let q = sample_point(&montgomery);
+            let (p_ed, q_ed) = (edwards.from_montgomery_point(&p), edwards.from_montgomery_point(&q));
+
+            let sum = edwards.add_points(p_ed.clone(), q_ed.clone());
+            assert!(edwards.is_on_curve(&sum));
+            assert_eq!(edwards.to_montgomery_point(&sum), montgomery.add_points(p.clone(), q));
+
+            // the addition is unified: it also doubles, and adds the neutral point and opposite points
+            assert_eq!(edwards.double_point(p_ed.clone()), edwards.add_points(p_ed.clone(), p_ed.clone()));
+            assert_eq!(edwards.to_montgomery_point(&edwards.double_point(p_ed.clone())), montgomery.add_points(p.clone(), p.clone()));
+            assert_eq!(edwards.add_points(p_ed.clone(), ExtendedPoint::neutral_point()), p_ed);
+            assert_eq!(edwards.add_points(p_ed.clone(), edwards.neg_point(p_ed.clone())), ExtendedPoint::neutral_point());
+        }
+    }
+}
+
+#[test]
+fn scalar_mult_coincide_with_montgomery(){
+    for _j in 0..10{
+        let edwards = sample_edwards_curve();
+        let montgomery = edwards.to_montgomery();
+        for _i in 0..10{
+            let p = sample_point(&montgomery);
+            let n = Integer::sample_uniform(&Integer::from(-2*P as i64), &Integer::from(2*P));
+            let np = edwards.scalar_mult(n.clone(), edwards.from_montgomery_point(&p));
+            assert_eq!(edwards.to_montgomery_point(&np), montgomery.scalar_mult(n, p));
+        }
+    }
+}
+
+#[test]
+fn batch_to_affine_coincide(){
+    let edwards = sample_edwards_curve();
+    let montgomery = edwards.to_montgomery();
+    let points : Vec<ExtendedPoint<K>> = (0..30).map(|i| {
+        edwards.scalar_mult(Integer::from(3 + i), edwards.from_montgomery_point(&sample_point(&montgomery)))
+    }).collect();
+    let expected : Vec<(K, K)> = points.iter().map(|p| p.to_affine()).collect();
+    assert_eq!(ExtendedPoint::batch_to_affine(&points), expected);
+}
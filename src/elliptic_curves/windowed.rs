@@ -0,0 +1,124 @@
+use crate::field::IntegerTrait;
+use crate::integers::bit_length;
+
+use super::*;
+use super::projective::JacobianPoint;
+
+// Width-w non-adjacent form of n >= 0, least significant digit first: every non zero digit is odd,
+// of absolute value below 2^(w-1), and is followed by at least w-1 zeros
+pub fn wnaf<Integer : IntegerTrait>(n : &Integer, w : u32) -> Vec<i64>{
+    assert!(*n >= Integer::from(0) && (2..=32).contains(&w));
+    let mut digits = Vec::new();
+    let mut m = n.clone();
+    while m != Integer::from(0){
+        let mut digit = 0i64;
+        if m.clone()%2 != Integer::from(0){
+            digit = (m.clone()%(1u64 << w)).to_u64().unwrap() as i64;
+            if digit >= 1 << (w-1){
+                digit -= 1 << w;
+            }
+            if digit >= 0{
+                m -= Integer::from(digit as u64);
+            }else{
+                m += Integer::from((-digit) as u64);
+            }
+        }
+        digits.push(digit);
+        m >>= 1;
+    }
+    digits
+}
+
+// Precomputed points for the fixed-base comb method of Lim and Lee: the scalar bits are split into
+// window rows of columns bits, and points[i] = sum_j i_j*[2^(j*columns)]base for 0 < i < 2^window
+#[derive(Debug, Clone)]
+pub struct CombTable<K>{
+    pub window : u32,
+    pub columns : u32,
+    pub points : Vec<ProjKPoint<K>>,
+}
+
+impl<K> EllipticCurve<K>
+    where K : Field{
+
+        // The odd multiples [1]p, [3]p, ..., [2^(w-1) - 1]p
+        pub fn odd_multiples(&self, p : &ProjKPoint<K>, w : u32) -> Vec<ProjKPoint<K>>{
+            let p2 = self.add_points(p.clone(), p.clone());
+            let mut multiples = vec![p.clone()];
+            for i in 1..(1usize << (w-2)){
+                multiples.push(self.add_points(multiples[i-1].clone(), p2.clone()));
+            }
+            multiples
+        }
+
+        // Left-to-right scalar multiplication on the width-w NAF of n, with w >= 2
+        pub fn scalar_mult_wnaf(&self, n : K::Integer, point : ProjKPoint<K>, w : u32) -> JacobianPoint<K>{
+            assert!(self.is_on_curve(&point));
+            if n < K::Integer::from(0){
+                return self.scalar_mult_wnaf(-n, self.neg_point(point), w);
+            }
+
+            let multiples = self.odd_multiples(&point, w);
+            let mut result = JacobianPoint::infinite_point();
+            for digit in wnaf(&n, w).into_iter().rev(){
+                result = self.jacobian_double(result);
+                if digit > 0{
+                    result = self.jacobian_add_affine(result, &multiples[(digit as usize - 1)/2]);
+                }else if digit < 0{
+                    result = self.jacobian_add_affine(result, &self.neg_point(multiples[((-digit) as usize - 1)/2].clone()));
+                }
+            }
+            result
+        }
+
+        // Comb table of base for scalars of at most bits bits
+        pub fn comb_table(&self, base : &ProjKPoint<K>, window : u32, bits : u32) -> CombTable<K>{
+            assert!(self.is_on_curve(base) && window >= 1);
+            let columns = bits.div_ceil(window);
+
+            // rows[j] = [2^(j*columns)]base
+            let mut rows = vec![base.clone()];
+            for j in 1..window as usize{
+                let mut row = rows[j-1].clone();
+                for _k in 0..columns{
+                    row = self.add_points(row.clone(), row);
+                }
+                rows.push(row);
+            }
+
+            let mut points = vec![ProjKPoint::InfPoint];
+            for i in 1..(1usize << window){
+                let j = i.trailing_zeros() as usize;
+                points.push(self.add_points(points[i & (i-1)].clone(), rows[j].clone()));
+            }
+            CombTable{ window, columns, points }
+        }
+
+        // [n]base with the comb table of base, using columns doublings
+        pub fn scalar_mult_comb(&self, n : K::Integer, table : &CombTable<K>) -> JacobianPoint<K>{
+            if n < K::Integer::from(0){
+                let JacobianPoint{ x, y, z } = self.scalar_mult_comb(-n, table);
+                return JacobianPoint{ x, y: -y, z };
+            }
+            assert!(bit_length(&n) <= (table.window*table.columns) as usize, "scalar too large for the comb table");
+
+            let bit = |k : u32| (n.clone() >> k as usize)%2 != K::Integer::from(0);
+            let mut result = JacobianPoint::infinite_point();
+            for column in (0..table.columns).rev(){
+                result = self.jacobian_double(result);
+                let mut index = 0;
+                for row in 0..table.window{
+                    if bit(row*table.columns + column){
+                        index |= 1 << row;
+                    }
+                }
+                if index != 0{
+                    result = self.jacobian_add_affine(result, &table.points[index]);
+                }
+            }
+            result
+        }
+}
+
+#[cfg(all(test, feature = "gmp"))]
+mod test;
@@ -0,0 +1,69 @@
+use super::*;
+
+use crate::finite_fields::*;
+
+pub type Integer = gmp::mpz::Mpz;
+
+const P : u32 = 10169;
+
+declare_finite_field!(K, Integer, Integer::from(P), m10169);
+
+fn sample_element() -> K{
+    K::new(Integer::sample_uniform(&Integer::from(0), &Integer::from(P)))
+}
+
+fn sample_curve() -> EllipticCurve<K>{
+    loop{
+        let ell = EllipticCurve::new_reduced_weierstrass(sample_element(), sample_element());
+        if ell.discriminant() != K::from_int(0){
+            return ell;
+        }
+    }
+}
+
+#[test]
+fn wnaf_digits(){
+    for w in 2..8{
+        for _i in 0..100{
+            let n = Integer::sample_uniform(&Integer::from(0), &(Integer::from(1) << 100));
+            let digits = wnaf(&n, w);
+            let mut m = Integer::from(0);
+            for (i, digit) in digits.iter().enumerate().rev(){
+                m = m*Integer::from(2) + Integer::from(*digit as i32);
+                if *digit != 0{
+                    assert!(digit%2 != 0 && digit.abs() < 1 << (w-1));
+                    assert!(digits[i.saturating_sub(w as usize - 1)..i].iter().all(|d| *d == 0));
+                }
+            }
+            assert_eq!(m, n);
+        }
+    }
+    assert_eq!(wnaf(&Integer::from(7), 2), vec![-1, 0, 0, 1]);
+}
+
+#[test]
+fn scalar_mult_wnaf_coincide(){
+    for _j in 0..10{
+        let ell = sample_curve();
+        for w in 2..6{
+            let p = ell.sample_point();
+            let n = Integer::sample_uniform(&Integer::from(-2*P as i64), &Integer::from(2*P));
+            assert_eq!(ell.scalar_mult_wnaf(n.clone(), p.clone(), w).to_affine(), ell.scalar_mult(n, p));
+        }
+    }
+}
+
+#[test]
+fn scalar_mult_comb_coincide(){
+    for _j in 0..10{
+        let ell = sample_curve();
+        let p = ell.sample_point();
+        for window in 1..6{
+            let table = ell.comb_table(&p, window, 16);
+            for _i in 0..10{
+                let n = Integer::sample_uniform(&Integer::from(-(1 << 16)), &Integer::from(1u32 << 16));
+                assert_eq!(ell.scalar_mult_comb(n.clone(), &table).to_affine(), ell.scalar_mult(n, p.clone()));
+            }
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use std::fmt;
+
+// Failures of the fallible (try_ and checked_) operations, which return them instead of panicking so that
+// long computations over rings which are not fields (ECM, trial isogenies) can recover
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error{
+    NotInvertible, // zero, or a non unit of Z/nZ for a composite n
+    NotOnCurve,
+    NotReducedWeierstrass,
+    UnsupportedEmbeddingDegree,
+}
+
+impl fmt::Display for Error{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self{
+            Error::NotInvertible => write!(f, "the element is not invertible"),
+            Error::NotOnCurve => write!(f, "the point is not on the curve"),
+            Error::NotReducedWeierstrass => write!(f, "the curve is not in reduced Weierstrass form"),
+            Error::UnsupportedEmbeddingDegree => write!(f, "the embedding degree is not supported"),
+        }
+    }
+}
+
+impl std::error::Error for Error{}
@@ -9,9 +9,15 @@ use std::ops::{Add, Sub, Mul,
 use std::cmp::{PartialOrd};
 
 use std::fmt::Display;
+use std::hash::Hash;
+use std::str::FromStr;
 use std::marker::Sized;
+#[cfg(feature = "gmp")]
 use gmp::mpz::{Mpz};
 
+use crate::error::Error;
+use crate::constant_time::{limbs_from_le_bytes, limbs_to_le_bytes};
+
 pub trait Field : Sized + 
                   Add<Output=Self> +
                   Sub<Output=Self> +
@@ -24,6 +30,8 @@ pub trait Field : Sized +
                   DivAssign +
                   SubAssign +
                   PartialEq +
+                  Eq +
+                  Hash +
                   Display +
 
                   Clone +
@@ -32,9 +40,18 @@ pub trait Field : Sized +
                     type Integer : IntegerTrait;
                     fn from_int(n : i32) -> Self;
                     fn new(n : Self::Integer) -> Self;
+
+                    // Inverse without panicking on zero, nor on the non units when the modulus is composite
+                    fn try_inv(self) -> Result<Self, Error>{
+                        if self == Self::from_int(0){
+                            Err(Error::NotInvertible)
+                        }else{
+                            Ok(Self::from_int(1)/self)
+                        }
+                    }
                   }
 
-pub trait IntegerTrait : Sized + 
+pub trait IntegerTrait : 'static + Send + Sync + Sized + 
                   Add<Output=Self> +
                   Sub<Output=Self> +
                   Mul<Output=Self> +
@@ -55,19 +72,113 @@ pub trait IntegerTrait : Sized +
                   ShlAssign<usize> +
 
                   PartialEq +
+                  Eq +
                   PartialOrd +
+                  Hash +
 
                   Clone +
 
                   Display + 
+                  FromStr +
                   From<u32> + From<i32> + From<u32> + From<u64>{
 
                     fn sample_uniform(min : &Self, max : &Self) -> Self;
 
+                    fn to_u64(&self) -> Option<u64>;
+
+                    // Modular arithmetic used by the prime fields, on operands in [0, n). Backends override
+                    // these hooks with faster versions, like GMP's for Mpz or Montgomery multiplication on limbs
+
+                    fn add_mod(&self, other : &Self, n : &Self) -> Self{
+                      let sum = self.clone() + other.clone();
+                      if sum >= *n { sum - n.clone() } else { sum }
+                    }
+
+                    fn sub_mod(&self, other : &Self, n : &Self) -> Self{
+                      if self >= other { self.clone() - other.clone() } else { self.clone() - other.clone() + n.clone() }
+                    }
+
+                    // Without the full product when the backend can
+                    fn mul_mod(&self, other : &Self, n : &Self) -> Self{
+                      (self.clone()*other.clone())%n.clone()
+                    }
+
+                    // Whether the backend overrides powm with a faster exponentiation than square and multiply
+                    // on the field elements, which the fields then call
+                    const OVERRIDES_POWM : bool = false;
+
+                    // self^exponent mod n, for exponent >= 0
+                    fn powm(&self, exponent : &Self, n : &Self) -> Self{
+                      crate::integers::mod_pow(self.clone(), exponent.clone(), n)
+                    }
+
+                    // Inverse of self modulo n, None if they are not coprime
+                    fn invert(&self, n : &Self) -> Option<Self>{
+                      crate::integers::mod_inverse(self.clone(), n)
+                    }
+
+                    // Exchange a and b when choice is 1, and not when it is 0. Backends of constant-time fields
+                    // override it with masks instead of a branch on choice
+                    fn conditional_swap(a : &mut Self, b : &mut Self, choice : u64){
+                      if choice == 1{
+                        std::mem::swap(a, b);
+                      }
+                    }
+
+                    // The reductions on limbs (Montgomery form, special forms) see a non negative integer below
+                    // 2^(64*len) as len little-endian 64-bit limbs. f gets at least len limbs, the extra ones being 0
+                    fn with_limbs<R, F : FnOnce(&[u64]) -> R>(&self, len : usize, f : F) -> R{
+                      assert!(*self >= Self::from(0), "the limbs of a negative integer");
+                      let mut limbs = vec![0; len];
+                      limbs_from_le_bytes(&crate::integers::to_le_bytes(self, 8*len), &mut limbs);
+                      f(&limbs)
+                    }
+
+                    // The integer whose limbs are written by f on at least len zeroed limbs
+                    fn from_limbs_with<F : FnOnce(&mut [u64])>(len : usize, f : F) -> Self{
+                      let mut limbs = vec![0; len];
+                      f(&mut limbs);
+                      crate::integers::from_le_bytes(&limbs_to_le_bytes(&limbs, 8*len))
+                    }
+
                   }
 
 
+// Inverts in place all the non zero elements with a single inversion, by Montgomery's simultaneous
+// inversion trick, at the cost of 3 multiplications per element. Zeros are left unchanged
+pub fn batch_inv<K : Field>(elements : &mut [K]){
+  let zero = K::from_int(0);
+  // products of the non zero elements before each position
+  let mut prefixes = Vec::with_capacity(elements.len());
+  let mut product = K::from_int(1);
+  for x in elements.iter(){
+    prefixes.push(product.clone());
+    if *x != zero{
+      product *= x.clone();
+    }
+  }
+
+  // inverse is the inverse of the product of the non zero elements up to x
+  let mut inverse = K::from_int(1)/product;
+  for (x, prefix) in elements.iter_mut().zip(prefixes).rev(){
+    if *x != zero{
+      let x_inv = inverse.clone()*prefix;
+      inverse *= x.clone();
+      *x = x_inv;
+    }
+  }
+}
 
+// f(point, 1/z(point)) for all the points with a single inversion, the inverse being 0 when z(point) is 0,
+// as in the conversions of batches of projective points to affine coordinates
+pub fn map_with_inverses<P, K : Field, T>(points : &[P], z : impl Fn(&P) -> K, f : impl Fn(&P, K) -> T) -> Vec<T>{
+  let mut inverses : Vec<K> = points.iter().map(z).collect();
+  batch_inv(&mut inverses);
+  points.iter().zip(inverses).map(|(point, inverse)| f(point, inverse)).collect()
+}
+
+
+#[cfg(feature = "gmp")]
 impl IntegerTrait for Mpz{
   fn sample_uniform(min : &Mpz, max : &Mpz) -> Mpz{
     let mut u : [u8; 64] = [0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0];
@@ -78,4 +189,49 @@ impl IntegerTrait for Mpz{
     let z = Mpz::from(v);
     z%(&(max-min)) + min
   }
+
+  fn to_u64(&self) -> Option<u64>{
+    Option::<u64>::from(self)
+  }
+
+  fn add_mod(&self, other : &Mpz, n : &Mpz) -> Mpz{
+    let sum = self + other;
+    if &sum >= n { sum - n } else { sum }
+  }
+
+  fn sub_mod(&self, other : &Mpz, n : &Mpz) -> Mpz{
+    if self >= other { self - other } else { self - other + n }
+  }
+
+  fn mul_mod(&self, other : &Mpz, n : &Mpz) -> Mpz{
+    (self*other)%n
+  }
+
+  const OVERRIDES_POWM : bool = true;
+
+  fn powm(&self, exponent : &Mpz, n : &Mpz) -> Mpz{
+    Mpz::powm(self, exponent, n)
+  }
+
+  fn invert(&self, n : &Mpz) -> Option<Mpz>{
+    Mpz::invert(self, n)
+  }
+
+  // Through GMP's export and import of big-endian bytes
+  fn with_limbs<R, F : FnOnce(&[u64]) -> R>(&self, len : usize, f : F) -> R{
+    assert!(*self >= Mpz::zero(), "the limbs of a negative integer");
+    let mut bytes = Vec::<u8>::from(self);
+    bytes.reverse();
+    let mut limbs = vec![0; len.max(bytes.len().div_ceil(8))];
+    limbs_from_le_bytes(&bytes, &mut limbs);
+    f(&limbs)
+  }
+
+  fn from_limbs_with<F : FnOnce(&mut [u64])>(len : usize, f : F) -> Mpz{
+    let mut limbs = vec![0; len];
+    f(&mut limbs);
+    let mut bytes = limbs_to_le_bytes(&limbs, 8*len);
+    bytes.reverse();
+    Mpz::from(&bytes[..])
+  }
 }
@@ -1,22 +1,38 @@
 use std::ops::{Add, Sub, Mul, Div, Neg, AddAssign, MulAssign, DivAssign, SubAssign};
 use std::clone::Clone;
 use std::fmt;
-
-use gmp::mpz::Mpz;
+use std::hash::{Hash, Hasher};
 
 use num_traits::ops::inv::Inv;
 
 use std::marker::PhantomData;
 
 use crate::field::{Field, IntegerTrait};
+use crate::error::Error;
+use crate::integers::jacobi_symbol;
+
+pub mod montgomery;
+pub mod special_form;
 
-pub mod integer_mpz;
+pub use montgomery::MontgomeryParameters;
+pub use special_form::SpecialFormParameters;
+
+// How the representatives of Fp are stored and reduced after a product
+pub enum Reduction{
+    Division, // canonical representatives, products reduced by mul_mod
+    Montgomery(&'static MontgomeryParameters), // representatives a*R mod p, products reduced by REDC
+    SpecialForm(&'static SpecialFormParameters), // canonical representatives, products reduced by folding
+}
 
 pub trait IntegerAsType<Integer : IntegerTrait>{
     fn value() -> Integer;
+
+    fn reduction() -> Reduction{
+        Reduction::Division
+    }
 }
 
-#[derive(Debug, Hash)]
+#[derive(Debug)]
 pub struct Fp<N : IntegerAsType<Integer>, Integer : IntegerTrait>{
     repr : Integer,
     _phantom : PhantomData<N>,
@@ -40,9 +56,67 @@ macro_rules! declare_finite_field {
 
 
         pub type $name = Fp<$m::TypeInt, $integer>;
-    }
+    };
+    // Elements stored in Montgomery form, for an odd p
+    ($name: ident, $integer: ident, $p: expr, $m:ident, montgomery) => {
+        mod $m{
+            use super::*;
+
+            #[derive(Debug, Hash)]
+            pub struct TypeInt{}
+
+            impl IntegerAsType<$integer> for TypeInt{
+                fn value() -> $integer{
+                    $p
+                }
+
+                fn reduction() -> $crate::finite_fields::Reduction{
+                    static PARAMETERS : std::sync::OnceLock<$crate::finite_fields::MontgomeryParameters> = std::sync::OnceLock::new();
+                    $crate::finite_fields::Reduction::Montgomery(PARAMETERS.get_or_init(|| $crate::finite_fields::MontgomeryParameters::new(&$p)))
+                }
+            }
+        }
+
+
+        pub type $name = Fp<$m::TypeInt, $integer>;
+    };
+    // p = 2^k - c for a small c
+    ($name: ident, $integer: ident, $p: expr, $m:ident, pseudo_mersenne($k: expr, $c: expr)) => {
+        $crate::declare_finite_field!(@special_form $name, $integer, $p, $m,
+            $crate::finite_fields::SpecialFormParameters::pseudo_mersenne(&$p, $k, $c));
+    };
+    // p = 2^k - sum of sign*2^exponent, for k and exponents multiples of 32
+    ($name: ident, $integer: ident, $p: expr, $m:ident, solinas($k: expr, [$(($sign: expr, $exponent: expr)),*])) => {
+        $crate::declare_finite_field!(@special_form $name, $integer, $p, $m,
+            $crate::finite_fields::SpecialFormParameters::solinas(&$p, $k, &[$(($sign, $exponent)),*]));
+    };
+    (@special_form $name: ident, $integer: ident, $p: expr, $m:ident, $parameters: expr) => {
+        mod $m{
+            use super::*;
+
+            #[derive(Debug, Hash)]
+            pub struct TypeInt{}
+
+            impl IntegerAsType<$integer> for TypeInt{
+                fn value() -> $integer{
+                    $p
+                }
+
+                fn reduction() -> $crate::finite_fields::Reduction{
+                    static PARAMETERS : std::sync::OnceLock<$crate::finite_fields::SpecialFormParameters> = std::sync::OnceLock::new();
+                    $crate::finite_fields::Reduction::SpecialForm(PARAMETERS.get_or_init(|| $parameters))
+                }
+            }
+        }
+
+
+        pub type $name = Fp<$m::TypeInt, $integer>;
+    };
 }
 
+pub mod quadratic_extension;
+pub mod runtime;
+
 pub trait FiniteField : Field{
 
     fn legendre_symbol(&self) -> i8;
@@ -54,17 +128,27 @@ pub trait FiniteField : Field{
     fn cardinal() -> Self::Integer;
 
     fn sign(&self) -> bool; // true = +, false = -, + = closest to 0
+
+    fn to_integer(&self) -> Self::Integer; // canonical representative in [0, cardinal)
+
+    // Exchange a and b when choice is 1, and not when it is 0, without branching on choice in the fields over
+    // constant-time backends
+    fn conditional_swap(a : &mut Self, b : &mut Self, choice : u64){
+        if choice == 1{
+            std::mem::swap(a, b);
+        }
+    }
 }
 
 impl<N : IntegerAsType<Integer>, Integer : IntegerTrait> fmt::Display for Fp<N, Integer>{
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            write!(f, "{}", self.repr)
+            write!(f, "{}", self.to_integer())
         }
 }
 
 impl<N : IntegerAsType<Integer>, Integer : IntegerTrait> Clone for Fp<N, Integer>{
         fn clone(&self) -> Fp<N, Integer>{
-            Fp::new(self.repr.clone())
+            Fp::from_repr(self.repr.clone())
         }
 }
 
@@ -72,15 +156,15 @@ impl<N : IntegerAsType<Integer>, Integer : IntegerTrait> Add for Fp<N, Integer>{
         type Output = Fp<N, Integer>;
 
         fn add(self, other: Fp<N, Integer>) -> Fp<N, Integer>{
-            Fp::new((self.repr + other.repr) % N::value())
+            Fp::from_repr(self.repr.add_mod(&other.repr, &N::value()))
         }
 }
 
 impl<N : IntegerAsType<Integer>, Integer : IntegerTrait> Add for &Fp<N, Integer>{
         type Output = Fp<N, Integer>;
 
-        default fn add(self, other: &Fp<N, Integer>) -> Fp<N, Integer>{
-            self.clone()+other.clone()
+        fn add(self, other: &Fp<N, Integer>) -> Fp<N, Integer>{
+            Fp::from_repr(self.repr.add_mod(&other.repr, &N::value()))
         }
 }
 
@@ -112,19 +196,15 @@ impl<N : IntegerAsType<Integer>, Integer : IntegerTrait> Sub for Fp<N, Integer>{
         type Output = Fp<N, Integer>;
 
         fn sub(self, other: Fp<N, Integer>) -> Fp<N, Integer>{
-            if self.repr > other.repr{
-                Fp::new(self.repr - other.repr)
-            }else{
-                Fp::new(self.repr - other.repr + N::value())
-            }
+            Fp::from_repr(self.repr.sub_mod(&other.repr, &N::value()))
         }
 }
 
 impl<N : IntegerAsType<Integer>, Integer : IntegerTrait> Sub for &Fp<N, Integer>{
         type Output = Fp<N, Integer>;
 
-        default fn sub(self, other: &Fp<N, Integer>) -> Fp<N, Integer>{
-            self.clone()-other.clone()
+        fn sub(self, other: &Fp<N, Integer>) -> Fp<N, Integer>{
+            Fp::from_repr(self.repr.sub_mod(&other.repr, &N::value()))
         }
 }
 
@@ -132,41 +212,24 @@ impl<N : IntegerAsType<Integer>, Integer : IntegerTrait> Mul for Fp<N, Integer>{
         type Output = Fp<N, Integer>;
 
         fn mul(self, other: Fp<N, Integer>) -> Fp<N, Integer>{
-            Fp::new((self.repr*other.repr) % N::value())
+            Fp::from_repr(Fp::<N, Integer>::mul_repr(&self.repr, &other.repr))
         }
 }
 
 impl<N : IntegerAsType<Integer>, Integer : IntegerTrait> Mul for &Fp<N, Integer>{
         type Output = Fp<N, Integer>;
 
-        default fn mul(self, other: &Fp<N, Integer>) -> Fp<N, Integer>{
-            self.clone()*other.clone()
+        fn mul(self, other: &Fp<N, Integer>) -> Fp<N, Integer>{
+            Fp::from_repr(Fp::<N, Integer>::mul_repr(&self.repr, &other.repr))
         }
 }
 
 impl<N : IntegerAsType<Integer>, Integer : IntegerTrait> num_traits::ops::inv::Inv for Fp<N, Integer>{
     type Output = Self;
     
-    default fn inv(self) -> Fp<N, Integer>{
+    fn inv(self) -> Fp<N, Integer>{
         assert!(self.repr != Integer::from(0));
-        let (mut t, mut new_t) = (Integer::from(0), Integer::from(1));
-        let (mut r, mut new_r) = (N::value(), self.repr.clone());
-        
-        while new_r != Integer::from(0){
-            let quotient = r.clone() / new_r.clone();
-            
-            let old_t = t;
-            t = new_t.clone();
-            new_t = old_t - quotient.clone()*new_t;
-
-            let old_r = r;
-            r = new_r.clone();
-            new_r = old_r - quotient*new_r;
-        }
-        if t < Integer::from(0){
-            t += N::value();
-        }
-        Fp::new(t)
+        Fp::new(self.to_integer().invert(&N::value()).unwrap())
     }
 }
 
@@ -182,7 +245,11 @@ impl<N : IntegerAsType<Integer>, Integer : IntegerTrait> Neg for Fp<N, Integer>{
         type Output = Fp<N, Integer>;
 
         fn neg(self) -> Fp<N, Integer>{
-            Fp::new(-self.repr)
+            if self.repr == Integer::from(0){
+                self
+            }else{
+                Fp::from_repr(N::value() - self.repr)
+            }
         }
 }
 
@@ -192,144 +259,173 @@ impl<N : IntegerAsType<Integer>, Integer : IntegerTrait> PartialEq for Fp<N, Int
         }
 }
 
+impl<N : IntegerAsType<Integer>, Integer : IntegerTrait> Eq for Fp<N, Integer>{}
+
+// Only the representative is hashed, consistently with PartialEq
+impl<N : IntegerAsType<Integer>, Integer : IntegerTrait> Hash for Fp<N, Integer>{
+        fn hash<H : Hasher>(&self, state: &mut H){
+            self.repr.hash(state);
+        }
+}
+
+impl<N : IntegerAsType<Integer>, Integer : IntegerTrait> Fp<N, Integer>{
+    // The representative must already be reduced, and in Montgomery form when N asks for it
+    fn from_repr(repr : Integer) -> Fp<N, Integer>{
+        Fp{
+            repr,
+            _phantom: PhantomData,
+        }
+    }
+
+    fn mul_repr(a : &Integer, b : &Integer) -> Integer{
+        match N::reduction(){
+            Reduction::Division => a.mul_mod(b, &N::value()),
+            Reduction::Montgomery(parameters) => parameters.mul(a, b),
+            Reduction::SpecialForm(parameters) => parameters.mul(a, b),
+        }
+    }
+}
+
 impl<N : IntegerAsType<Integer>, Integer : IntegerTrait> Field for Fp<N, Integer> {
     type Integer=Integer;
 
     fn new(x : Integer) -> Fp<N, Integer>{
-        if Integer::from(0) <= x && x < N::value(){
-            Fp{
-                repr: x,
-                _phantom: PhantomData,
-            }
+        let x = if Integer::from(0) <= x && x < N::value(){
+            x
         }else{
             let y = x%N::value();
             if y < Integer::from(0){
-                Fp{
-                    repr: y + N::value(),
-                    _phantom: PhantomData,
-                }
+                y + N::value()
             }else{
-                Fp{
-                    repr: y,
-                    _phantom: PhantomData,
-                }
+                y
             }
+        };
+        match N::reduction(){
+            Reduction::Montgomery(parameters) => Fp::from_repr(parameters.to_montgomery(&x)),
+            _ => Fp::from_repr(x),
         }
     }
 
     fn from_int(n : i32) -> Fp<N, Integer> {
         Fp::new(Integer::from(n))
     }
+
+    // Also meaningful when N is composite, as in the factorization methods
+    fn try_inv(self) -> Result<Fp<N, Integer>, Error>{
+        self.to_integer().invert(&N::value()).map(Fp::new).ok_or(Error::NotInvertible)
+    }
 }
 
 impl<N : IntegerAsType<Integer>, Integer : IntegerTrait> FiniteField for Fp<N, Integer>{
 
-    default fn legendre_symbol(&self) -> i8{
-        let mut a = self.repr.clone();
-        let mut m = N::value();
-        let mut t : i8 = 1;
-        while a != Integer::from(0){
-            while a.clone()%2 == Integer::from(0){
-                a = a/Integer::from(2);
-                if m.clone()%8 == Integer::from(3) || m.clone()%8 == Integer::from(5){
-                    t = -t;
-                }
-            }
-            let _c = a;
-            a = m;
-            m = _c;
-
-            if a.clone()%4 == Integer::from(3) && m.clone()%4 == Integer::from(3){
-                t = -t;
-            }
-            a = a%m.clone();
-        }
-        if m==Integer::from(1){
-            t
-        }else{
-            0
-        }
+    fn legendre_symbol(&self) -> i8{
+        jacobi_symbol(self.to_integer(), N::value())
     }
 
-    default fn cardinal() -> Integer{
+    fn cardinal() -> Integer{
         N::value()
     }
 
-    default fn exp(a : Fp<N, Integer>, n : Integer) -> Fp<N, Integer>{
-        if n == Integer::from(0){
-            return Fp::from_int(1);
-        }
-        if n<Integer::from(0){
+    fn exp(a : Fp<N, Integer>, n : Integer) -> Fp<N, Integer>{
+        if n < Integer::from(0){
             return Fp::exp(a.inv(), -n);
         }
-        if n.clone()%2 == Integer::from(0){
-            Fp::exp(&a*&a, n/Integer::from(2))
-        }else{
-            &a*&Fp::exp(&a*&a, (n-Integer::from(1))/Integer::from(2))
+        match N::reduction(){
+            Reduction::Division if Integer::OVERRIDES_POWM => Fp::new(a.to_integer().powm(&n, &N::value())),
+            _ => square_and_multiply(a, n, Fp::from_int(1)),
         }
     }
 
-    default fn square_root(self) -> Fp<N, Integer>{
+    fn square_root(self) -> Fp<N, Integer>{
+        square_root_mod_p(self)
+    }
 
-        let exp = Fp::exp;
+    fn sign(&self) -> bool {
+        self.to_integer() <= (N::value()-Integer::from(1))/Integer::from(2) // true if self is closer to 0 (0 is positive)
+    }
 
-        let p = N::value();
-        let a = self.clone();
+    fn conditional_swap(a : &mut Fp<N, Integer>, b : &mut Fp<N, Integer>, choice : u64){
+        Integer::conditional_swap(&mut a.repr, &mut b.repr, choice);
+    }
 
-        if p.clone()%8 == Integer::from(3) || p.clone()%8 == Integer::from(7){
-            return exp(a, (p.clone()+Integer::from(1))/Integer::from(4));
+    fn to_integer(&self) -> Integer {
+        match N::reduction(){
+            Reduction::Montgomery(parameters) => parameters.from_montgomery(&self.repr),
+            _ => self.repr.clone(),
         }
+    }
+}
 
-        if p.clone()%8 == Integer::from(5){
-            let mut x = exp(a, (p.clone()+Integer::from(3))/Integer::from(8));
-            let c = &x*&x;
-            if c != self{
-                x = &x*&exp(Fp::from_int(2), (p.clone()-Integer::from(1))/Integer::from(4));
-            }
-            return x;
+// a^n for n >= 0, with the products of the field, so that the elements stay in its representation
+fn square_and_multiply<K : Field>(a : K, n : K::Integer, one : K) -> K{
+    let (mut result, mut power, mut n) = (one, a, n);
+    while n > K::Integer::from(0){
+        if n.clone()%2 == K::Integer::from(1){
+            result *= power.clone();
+        }
+        n >>= 1;
+        if n > K::Integer::from(0){
+            power = power.clone()*power;
         }
+    }
+    result
+}
 
-        // Here p = 1 mod 8
-        let mut d = Fp::new(Integer::sample_uniform(&Integer::from(2), &(p.clone()-Integer::from(1))));
+// Square root of a square a of a prime field, the one closest to 0: by a single exponentiation when
+// p = 3 mod 4 or p = 5 mod 8, and by the Tonelli-Shanks algorithm when p = 1 mod 8
+fn square_root_mod_p<K : FiniteField>(a : K) -> K{
+    let exp = K::exp;
 
-        while d.legendre_symbol() != -1{
-            d = Fp::new(Integer::sample_uniform(&Integer::from(2), &(p.clone()-Integer::from(1))));
-        }
+    let p = K::cardinal();
 
-        let mut t = p-Integer::from(1);
-        let mut s = 0;
-        while t.clone()%2 == Integer::from(0){ // represent p-1 = t*2^s
-            s += 1;
-            t >>= 1;
+    if p.clone()%8 == K::Integer::from(3) || p.clone()%8 == K::Integer::from(7){
+        return exp(a, (p.clone()+K::Integer::from(1))/K::Integer::from(4));
+    }
+
+    if p.clone()%8 == K::Integer::from(5){
+        let mut x = exp(a.clone(), (p.clone()+K::Integer::from(3))/K::Integer::from(8));
+        let c = x.clone()*x.clone();
+        if c != a{
+            x *= exp(K::from_int(2), (p.clone()-K::Integer::from(1))/K::Integer::from(4));
         }
+        return x;
+    }
 
-        let big_a = exp(a.clone(), t.clone());
-        let big_d = exp(d, t.clone());
+    // Here p = 1 mod 8
+    let mut d = K::new(K::Integer::sample_uniform(&K::Integer::from(2), &(p.clone()-K::Integer::from(1))));
 
-        let mut m = Integer::from(0);
+    while d.legendre_symbol() != -1{
+        d = K::new(K::Integer::sample_uniform(&K::Integer::from(2), &(p.clone()-K::Integer::from(1))));
+    }
 
-        let mut exponent : Integer = Integer::from(1) << (s-1);
+    let mut t = p-K::Integer::from(1);
+    let mut s = 0;
+    while t.clone()%2 == K::Integer::from(0){ // represent p-1 = t*2^s
+        s += 1;
+        t >>= 1;
+    }
 
-        for i in 0..s{
-            if exp(big_a.clone()*exp(big_d.clone(), m.clone()), exponent.clone()) == Fp::from_int(-1){
-                m += Integer::from(1)<<i;
-            }
-            exponent >>= 1
-        }
-        
-        let sqr = exp(a, (t+Integer::from(1))/Integer::from(2))*exp(big_d, m/Integer::from(2));
-        if sqr.sign(){
-            sqr
-        }else{
-            -sqr
+    let big_a = exp(a.clone(), t.clone());
+    let big_d = exp(d, t.clone());
+
+    let mut m = K::Integer::from(0);
+
+    let mut exponent : K::Integer = K::Integer::from(1) << (s-1);
+
+    for i in 0..s{
+        if exp(big_a.clone()*exp(big_d.clone(), m.clone()), exponent.clone()) == K::from_int(-1){
+            m += K::Integer::from(1)<<i;
         }
+        exponent >>= 1
     }
 
-    default fn sign(&self) -> bool {
-        self.repr <= (N::value()-Integer::from(1))/Integer::from(2) // true if self is closer to 0 (0 is positive)
+    let sqr = exp(a, (t+K::Integer::from(1))/K::Integer::from(2))*exp(big_d, m/K::Integer::from(2));
+    if sqr.sign(){
+        sqr
+    }else{
+        -sqr
     }
 }
 
-
-#[cfg(test)]
+#[cfg(all(test, feature = "gmp"))]
 mod test;
\ No newline at end of file
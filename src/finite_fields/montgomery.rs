@@ -0,0 +1,36 @@
+use crate::field::IntegerTrait;
+use crate::constant_time::MontgomeryDomain;
+
+// Montgomery representation a*R mod n of the integers modulo an odd n, with R = 2^(64m) for the m limbs
+// of n. The arithmetic is the one of MontgomeryDomain, on the limbs of the integers
+#[derive(Debug)]
+pub struct MontgomeryParameters{
+    domain : MontgomeryDomain,
+}
+
+impl MontgomeryParameters{
+    pub fn new<Integer : IntegerTrait>(modulus : &Integer) -> MontgomeryParameters{
+        assert!(modulus.clone()%2 == Integer::from(1), "the Montgomery representation needs an odd modulus");
+        let limbs = crate::integers::bit_length(modulus).div_ceil(64);
+        MontgomeryParameters{
+            domain: modulus.with_limbs(limbs, MontgomeryDomain::new),
+        }
+    }
+
+    // a*R mod n, for 0 <= a < n
+    pub fn to_montgomery<Integer : IntegerTrait>(&self, a : &Integer) -> Integer{
+        let m = self.domain.limbs();
+        a.with_limbs(m, |a| Integer::from_limbs_with(m, |result| self.domain.to_montgomery(a, result)))
+    }
+
+    pub fn from_montgomery<Integer : IntegerTrait>(&self, a : &Integer) -> Integer{
+        let m = self.domain.limbs();
+        a.with_limbs(m, |a| Integer::from_limbs_with(m, |result| self.domain.from_montgomery(a, result)))
+    }
+
+    // Montgomery form of a*b from the Montgomery forms of a and b
+    pub fn mul<Integer : IntegerTrait>(&self, a : &Integer, b : &Integer) -> Integer{
+        let m = self.domain.limbs();
+        a.with_limbs(m, |a| b.with_limbs(m, |b| Integer::from_limbs_with(m, |result| self.domain.mul(a, b, result))))
+    }
+}
@@ -0,0 +1,299 @@
+use std::ops::{Add, Sub, Mul, Div, Neg, AddAssign, MulAssign, DivAssign, SubAssign};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use num_traits::ops::inv::Inv;
+
+use crate::field::{Field, IntegerTrait};
+use super::FiniteField;
+
+// Element re + im*sqrt(d) of K[sqrt(d)], where d is a fixed quadratic non residue of K. The elements outside K
+// carry d, computed once when they are sampled, and pass it on to the results of their operations. The elements
+// of K (from_base, from_int, new) do not need it
+#[derive(Debug, Clone)]
+pub struct QuadraticExtension<K : FiniteField>{
+    pub re : K,
+    pub im : K,
+    non_residue : Option<K>,
+}
+
+impl<K : FiniteField> QuadraticExtension<K>{
+    // -1 when p = 3 mod 4, otherwise the smallest non residue
+    pub fn non_residue() -> K{
+        if K::cardinal()%4 == K::Integer::from(3){
+            K::from_int(-1)
+        }else{
+            let mut d = 2;
+            while K::from_int(d).legendre_symbol() != -1{
+                d += 1;
+            }
+            K::from_int(d)
+        }
+    }
+
+    // d*x, d being computed again when the operands do not carry it, as for an element of K whose im was set
+    fn times_non_residue(x : K, non_residue : &Option<K>) -> K{
+        if x == K::from_int(0){
+            return x;
+        }
+        match non_residue{
+            Some(d) => d.clone()*x,
+            None => Self::non_residue()*x,
+        }
+    }
+
+    pub fn from_base(x : K) -> QuadraticExtension<K>{
+        QuadraticExtension{
+            re: x,
+            im: K::from_int(0),
+            non_residue: None,
+        }
+    }
+
+    pub fn conjugate(self) -> QuadraticExtension<K>{
+        QuadraticExtension{
+            re: self.re,
+            im: -self.im,
+            non_residue: self.non_residue,
+        }
+    }
+
+    pub fn norm(&self) -> K{
+        self.re.clone()*self.re.clone() - Self::times_non_residue(self.im.clone()*self.im.clone(), &self.non_residue)
+    }
+
+    pub fn sample_uniform() -> QuadraticExtension<K>{
+        Self::sample_with(Self::non_residue())
+    }
+
+    fn sample_with(non_residue : K) -> QuadraticExtension<K>{
+        let sample_element = | |{ K::new(K::Integer::sample_uniform(&K::Integer::from(0), &K::cardinal())) };
+        QuadraticExtension{
+            re: sample_element(),
+            im: sample_element(),
+            non_residue: Some(non_residue),
+        }
+    }
+}
+
+// The non residue is the same for all the elements, and is not compared
+impl<K : FiniteField> PartialEq for QuadraticExtension<K>{
+        fn eq(&self, other : &QuadraticExtension<K>) -> bool{
+            self.re == other.re && self.im == other.im
+        }
+}
+
+impl<K : FiniteField> Eq for QuadraticExtension<K>{}
+
+impl<K : FiniteField> Hash for QuadraticExtension<K>{
+        fn hash<H : Hasher>(&self, state: &mut H){
+            self.re.hash(state);
+            self.im.hash(state);
+        }
+}
+
+impl<K : FiniteField> fmt::Display for QuadraticExtension<K>{
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{} + {}*i", self.re, self.im)
+        }
+}
+
+impl<K : FiniteField> Add for QuadraticExtension<K>{
+        type Output = QuadraticExtension<K>;
+
+        fn add(self, other: QuadraticExtension<K>) -> QuadraticExtension<K>{
+            QuadraticExtension{
+                re: self.re + other.re,
+                im: self.im + other.im,
+                non_residue: self.non_residue.or(other.non_residue),
+            }
+        }
+}
+
+impl<K : FiniteField> Sub for QuadraticExtension<K>{
+        type Output = QuadraticExtension<K>;
+
+        fn sub(self, other: QuadraticExtension<K>) -> QuadraticExtension<K>{
+            QuadraticExtension{
+                re: self.re - other.re,
+                im: self.im - other.im,
+                non_residue: self.non_residue.or(other.non_residue),
+            }
+        }
+}
+
+impl<K : FiniteField> Mul for QuadraticExtension<K>{
+        type Output = QuadraticExtension<K>;
+
+        fn mul(self, other: QuadraticExtension<K>) -> QuadraticExtension<K>{
+            let non_residue = self.non_residue.or(other.non_residue);
+            QuadraticExtension{
+                re: self.re.clone()*other.re.clone() + Self::times_non_residue(self.im.clone()*other.im.clone(), &non_residue),
+                im: self.re*other.im + self.im*other.re,
+                non_residue,
+            }
+        }
+}
+
+impl<K : FiniteField> Inv for QuadraticExtension<K>{
+        type Output = QuadraticExtension<K>;
+
+        fn inv(self) -> QuadraticExtension<K>{
+            let norm_inv = K::from_int(1)/self.norm();
+            QuadraticExtension{
+                re: self.re*norm_inv.clone(),
+                im: -self.im*norm_inv,
+                non_residue: self.non_residue,
+            }
+        }
+}
+
+impl<K : FiniteField> Div for QuadraticExtension<K>{
+        type Output = QuadraticExtension<K>;
+
+        #[allow(clippy::suspicious_arithmetic_impl)]
+        fn div(self, other: QuadraticExtension<K>) -> QuadraticExtension<K>{
+            self*other.inv()
+        }
+}
+
+impl<K : FiniteField> Neg for QuadraticExtension<K>{
+        type Output = QuadraticExtension<K>;
+
+        fn neg(self) -> QuadraticExtension<K>{
+            QuadraticExtension{
+                re: -self.re,
+                im: -self.im,
+                non_residue: self.non_residue,
+            }
+        }
+}
+
+impl<K : FiniteField> AddAssign for QuadraticExtension<K>{
+        fn add_assign(&mut self, other: QuadraticExtension<K>){
+            *self = self.clone() + other;
+        }
+}
+
+impl<K : FiniteField> MulAssign for QuadraticExtension<K>{
+        fn mul_assign(&mut self, other: QuadraticExtension<K>){
+            *self = self.clone()*other;
+        }
+}
+
+impl<K : FiniteField> DivAssign for QuadraticExtension<K>{
+        fn div_assign(&mut self, other: QuadraticExtension<K>){
+            *self = self.clone()/other;
+        }
+}
+
+impl<K : FiniteField> SubAssign for QuadraticExtension<K>{
+        fn sub_assign(&mut self, other: QuadraticExtension<K>){
+            *self = self.clone()-other;
+        }
+}
+
+impl<K : FiniteField> Field for QuadraticExtension<K>{
+    type Integer = K::Integer;
+
+    fn new(x : K::Integer) -> QuadraticExtension<K>{
+        Self::from_base(K::new(x))
+    }
+
+    fn from_int(n : i32) -> QuadraticExtension<K>{
+        Self::from_base(K::from_int(n))
+    }
+}
+
+impl<K : FiniteField> FiniteField for QuadraticExtension<K>{
+
+    // a is a square in K[sqrt(d)] iff its norm is a square in K
+    fn legendre_symbol(&self) -> i8{
+        self.norm().legendre_symbol()
+    }
+
+    fn cardinal() -> K::Integer{
+        K::cardinal()*K::cardinal()
+    }
+
+    fn exp(a : QuadraticExtension<K>, n : K::Integer) -> QuadraticExtension<K>{
+        if n == K::Integer::from(0){
+            return Self::from_int(1);
+        }
+        if n < K::Integer::from(0){
+            return Self::exp(a.inv(), -n);
+        }
+        let square = Self::exp(a.clone()*a.clone(), n.clone()/K::Integer::from(2));
+        if n%2 == K::Integer::from(0){
+            square
+        }else{
+            a*square
+        }
+    }
+
+    // Tonelli-Shanks, the cardinal p^2 being always 1 mod 8
+    fn square_root(self) -> QuadraticExtension<K>{
+        let exp = Self::exp;
+        let one = Self::from_int(1);
+
+        if self == Self::from_int(0){
+            return self;
+        }
+
+        let mut t = Self::cardinal()-K::Integer::from(1);
+        let mut s = 0;
+        while t.clone()%2 == K::Integer::from(0){ // represent q-1 = t*2^s
+            s += 1;
+            t >>= 1;
+        }
+
+        let non_residue = self.non_residue.clone().unwrap_or_else(Self::non_residue);
+        let mut z = Self::sample_with(non_residue.clone());
+        while z.legendre_symbol() != -1{
+            z = Self::sample_with(non_residue.clone());
+        }
+
+        let mut m = s;
+        let mut c = exp(z, t.clone());
+        let mut x = exp(self.clone(), (t.clone()+K::Integer::from(1))/K::Integer::from(2));
+        let mut b = exp(self, t);
+
+        while b != one{
+            let mut i = 0;
+            let mut b_pow = b.clone();
+            while b_pow != one{
+                b_pow = b_pow.clone()*b_pow;
+                i += 1;
+            }
+            let mut w = c;
+            for _j in 0..(m-i-1){
+                w = w.clone()*w;
+            }
+            x *= w.clone();
+            c = w.clone()*w;
+            b *= c.clone();
+            m = i;
+        }
+
+        if x.sign(){
+            x
+        }else{
+            -x
+        }
+    }
+
+    fn sign(&self) -> bool{
+        if self.re == K::from_int(0){
+            self.im.sign()
+        }else{
+            self.re.sign()
+        }
+    }
+
+    fn to_integer(&self) -> K::Integer{
+        self.re.to_integer() + K::cardinal()*self.im.to_integer()
+    }
+}
+
+#[cfg(all(test, feature = "gmp"))]
+mod test;
@@ -0,0 +1,91 @@
+use super::*;
+
+use crate::finite_fields::*;
+
+pub type Integer = gmp::mpz::Mpz;
+
+declare_finite_field!(GL5483, Integer, Integer::from(5483), m5483); // = 3 mod 4
+declare_finite_field!(GL1009, Integer, Integer::from(1009), m1009); // = 1 mod 8
+
+fn sample<K : FiniteField>() -> QuadraticExtension<K>{
+    QuadraticExtension::sample_uniform()
+}
+
+#[test]
+fn non_residue_works(){
+    assert_eq!(QuadraticExtension::<GL5483>::non_residue(), GL5483::from_int(-1));
+    assert_eq!(QuadraticExtension::<GL1009>::non_residue().legendre_symbol(), -1);
+}
+
+#[test]
+fn elements_carry_the_non_residue(){
+    let d = QuadraticExtension::<GL1009>::non_residue();
+    let (a, b) = (sample::<GL1009>(), QuadraticExtension::<GL1009>::from_int(3));
+    assert_eq!(b.non_residue, None);
+    assert_eq!((b.clone()*a + b).non_residue, Some(d.clone()));
+    // an element of K whose im is set afterwards
+    let mut c = QuadraticExtension::<GL1009>::from_int(1);
+    c.im = GL1009::from_int(1);
+    let square = c.clone()*c;
+    assert_eq!((square.re, square.im), (GL1009::from_int(1) + d, GL1009::from_int(2)));
+}
+
+#[test]
+fn inverse_works(){
+    for _i in 0 .. 100{
+        let a = sample::<GL1009>();
+        if a != QuadraticExtension::from_int(0){
+            assert_eq!(a.clone()*a.inv(), QuadraticExtension::from_int(1));
+        }
+        let b = sample::<GL5483>();
+        if b != QuadraticExtension::from_int(0){
+            assert_eq!(b.clone()/b, QuadraticExtension::from_int(1));
+        }
+    }
+}
+
+#[test]
+fn fermat_little_theorem(){
+    for _i in 0 .. 20{
+        let a = sample::<GL1009>();
+        let q = QuadraticExtension::<GL1009>::cardinal();
+        assert_eq!(QuadraticExtension::exp(a.clone(), q), a);
+    }
+}
+
+#[test]
+fn base_field_elements_are_squares(){
+    for _i in 0 .. 20{
+        let a = QuadraticExtension::from_base(GL5483::new(Integer::sample_uniform(&Integer::from(1), &Integer::from(5483))));
+        assert_eq!(a.legendre_symbol(), 1);
+    }
+}
+
+#[test]
+fn square_root_works(){
+    for _i in 0 .. 20{
+        let a = sample::<GL1009>();
+        let sq = (a.clone()*a.clone()).square_root();
+        assert!(sq == a || sq == -a);
+
+        let b = sample::<GL5483>();
+        if b.legendre_symbol() == 1{
+            let sq = b.clone().square_root();
+            assert_eq!(sq.clone()*sq, b);
+        }
+    }
+}
+
+#[test]
+fn non_residue_follows_runtime_field(){
+    use crate::finite_fields::runtime::{FpContext, RuntimeFp};
+    // same base field type, one cardinal per context
+    for p in [1009, 1013, 5483, 1009]{
+        let context = FpContext::new(Integer::from(p));
+        RuntimeFp::with_context(&context, || {
+            let d = QuadraticExtension::<RuntimeFp<Integer>>::non_residue();
+            assert_eq!(d.context().modulus(), &Integer::from(p));
+            assert_eq!(d.legendre_symbol(), -1);
+        });
+    }
+}
@@ -0,0 +1,273 @@
+use std::ops::{Add, Sub, Mul, Div, Neg, AddAssign, MulAssign, DivAssign, SubAssign};
+use std::any::Any;
+use std::cell::RefCell;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use num_traits::ops::inv::Inv;
+
+use crate::field::{Field, IntegerTrait};
+use crate::error::Error;
+use crate::integers::{modulo, jacobi_symbol};
+use super::{FiniteField, square_root_mod_p, square_and_multiply};
+
+// Integers modulo a prime p chosen at run time, for instance read from a file or generated.
+// Every element holds the shared context of its modulus, and the operations between elements use it.
+// The constructors without an element at hand (new, from_int, cardinal, and so the generic code built on
+// them) use the innermost context entered with RuntimeFp::with_context on the current thread, see the
+// warning on the implementation of Field below
+#[derive(Debug)]
+pub struct FpContext<Integer : IntegerTrait>{
+    modulus : Integer,
+}
+
+#[derive(Debug)]
+pub struct RuntimeFp<Integer : IntegerTrait>{
+    repr : Integer,
+    context : Arc<FpContext<Integer>>,
+}
+
+thread_local!{
+    // Stack of the entered contexts, of any integer type
+    static CONTEXTS : RefCell<Vec<Arc<dyn Any + Send + Sync>>> = RefCell::new(Vec::new());
+}
+
+// Leaves the innermost context, even when the scope panics
+struct ContextGuard;
+
+impl Drop for ContextGuard{
+    fn drop(&mut self){
+        CONTEXTS.with(|contexts| {
+            contexts.borrow_mut().pop();
+        });
+    }
+}
+
+impl<Integer : IntegerTrait> FpContext<Integer>{
+    // p should be prime, as inversion and square roots assume it
+    pub fn new(p : Integer) -> Arc<FpContext<Integer>>{
+        assert!(p > Integer::from(1), "the modulus of a field must be at least 2");
+        Arc::new(FpContext{modulus: p})
+    }
+
+    pub fn modulus(&self) -> &Integer{
+        &self.modulus
+    }
+
+    pub fn element(self : &Arc<FpContext<Integer>>, x : Integer) -> RuntimeFp<Integer>{
+        RuntimeFp{
+            repr: modulo(x, &self.modulus),
+            context: self.clone(),
+        }
+    }
+}
+
+impl<Integer : IntegerTrait> RuntimeFp<Integer>{
+    // Runs f with context as the field of the static constructors
+    pub fn with_context<R, F : FnOnce() -> R>(context : &Arc<FpContext<Integer>>, f : F) -> R{
+        let context : Arc<dyn Any + Send + Sync> = context.clone();
+        CONTEXTS.with(|contexts| contexts.borrow_mut().push(context));
+        let _guard = ContextGuard;
+        f()
+    }
+
+    pub fn current_context() -> Arc<FpContext<Integer>>{
+        CONTEXTS.with(|contexts| contexts.borrow().last().cloned())
+            .expect("no runtime field context entered, see RuntimeFp::with_context")
+            .downcast::<FpContext<Integer>>()
+            .expect("the entered runtime field context has another integer type")
+    }
+
+    pub fn context(&self) -> &Arc<FpContext<Integer>>{
+        &self.context
+    }
+
+    fn modulus(&self) -> &Integer{
+        &self.context.modulus
+    }
+
+    // Element of the same field, from a reduced representative
+    fn with_repr(&self, repr : Integer) -> RuntimeFp<Integer>{
+        RuntimeFp{
+            repr,
+            context: self.context.clone(),
+        }
+    }
+
+    fn assert_same_field(&self, other : &RuntimeFp<Integer>){
+        assert!(Arc::ptr_eq(&self.context, &other.context) || self.modulus() == other.modulus(),
+            "operation between elements of different runtime fields");
+    }
+}
+
+impl<Integer : IntegerTrait> fmt::Display for RuntimeFp<Integer>{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.repr)
+    }
+}
+
+impl<Integer : IntegerTrait> Clone for RuntimeFp<Integer>{
+    fn clone(&self) -> RuntimeFp<Integer>{
+        self.with_repr(self.repr.clone())
+    }
+}
+
+impl<Integer : IntegerTrait> Add for RuntimeFp<Integer>{
+    type Output = RuntimeFp<Integer>;
+
+    fn add(self, other : RuntimeFp<Integer>) -> RuntimeFp<Integer>{
+        self.assert_same_field(&other);
+        self.with_repr(self.repr.add_mod(&other.repr, self.modulus()))
+    }
+}
+
+impl<Integer : IntegerTrait> Sub for RuntimeFp<Integer>{
+    type Output = RuntimeFp<Integer>;
+
+    fn sub(self, other : RuntimeFp<Integer>) -> RuntimeFp<Integer>{
+        self.assert_same_field(&other);
+        self.with_repr(self.repr.sub_mod(&other.repr, self.modulus()))
+    }
+}
+
+impl<Integer : IntegerTrait> Mul for RuntimeFp<Integer>{
+    type Output = RuntimeFp<Integer>;
+
+    fn mul(self, other : RuntimeFp<Integer>) -> RuntimeFp<Integer>{
+        self.assert_same_field(&other);
+        self.with_repr(self.repr.mul_mod(&other.repr, self.modulus()))
+    }
+}
+
+impl<Integer : IntegerTrait> Inv for RuntimeFp<Integer>{
+    type Output = RuntimeFp<Integer>;
+
+    fn inv(self) -> RuntimeFp<Integer>{
+        assert!(self.repr != Integer::from(0));
+        self.with_repr(self.repr.invert(self.modulus()).unwrap())
+    }
+}
+
+impl<Integer : IntegerTrait> Div for RuntimeFp<Integer>{
+    type Output = RuntimeFp<Integer>;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other : RuntimeFp<Integer>) -> RuntimeFp<Integer>{
+        self*other.inv()
+    }
+}
+
+impl<Integer : IntegerTrait> Neg for RuntimeFp<Integer>{
+    type Output = RuntimeFp<Integer>;
+
+    fn neg(self) -> RuntimeFp<Integer>{
+        if self.repr == Integer::from(0){
+            self
+        }else{
+            self.with_repr(self.modulus().clone() - self.repr.clone())
+        }
+    }
+}
+
+impl<Integer : IntegerTrait> AddAssign for RuntimeFp<Integer>{
+    fn add_assign(&mut self, other : RuntimeFp<Integer>){
+        *self = self.clone() + other;
+    }
+}
+
+impl<Integer : IntegerTrait> SubAssign for RuntimeFp<Integer>{
+    fn sub_assign(&mut self, other : RuntimeFp<Integer>){
+        *self = self.clone() - other;
+    }
+}
+
+impl<Integer : IntegerTrait> MulAssign for RuntimeFp<Integer>{
+    fn mul_assign(&mut self, other : RuntimeFp<Integer>){
+        *self = self.clone()*other;
+    }
+}
+
+impl<Integer : IntegerTrait> DivAssign for RuntimeFp<Integer>{
+    fn div_assign(&mut self, other : RuntimeFp<Integer>){
+        *self = self.clone()/other;
+    }
+}
+
+impl<Integer : IntegerTrait> PartialEq for RuntimeFp<Integer>{
+    fn eq(&self, other : &RuntimeFp<Integer>) -> bool{
+        self.repr == other.repr && self.modulus() == other.modulus()
+    }
+}
+
+impl<Integer : IntegerTrait> Eq for RuntimeFp<Integer>{}
+
+// Only the representative is hashed, consistently with PartialEq
+impl<Integer : IntegerTrait> Hash for RuntimeFp<Integer>{
+    fn hash<H : Hasher>(&self, state: &mut H){
+        self.repr.hash(state);
+    }
+}
+
+// Hazard: new, from_int and cardinal do not know the field of the elements around them. They read the
+// innermost context entered on the current thread, so that they panic outside of RuntimeFp::with_context,
+// build elements of the inner field in nested contexts, and panic in a newly spawned thread, which starts
+// without any context. So the generic algorithms spawning threads, as ecdlp_parallel_rho, do not run over RuntimeFp
+impl<Integer : IntegerTrait> Field for RuntimeFp<Integer>{
+    type Integer = Integer;
+
+    fn new(x : Integer) -> RuntimeFp<Integer>{
+        RuntimeFp::current_context().element(x)
+    }
+
+    fn from_int(n : i32) -> RuntimeFp<Integer>{
+        RuntimeFp::new(Integer::from(n))
+    }
+
+    fn try_inv(self) -> Result<RuntimeFp<Integer>, Error>{
+        match self.repr.invert(self.modulus()){
+            Some(inverse) => Ok(self.with_repr(inverse)),
+            None => Err(Error::NotInvertible),
+        }
+    }
+}
+
+impl<Integer : IntegerTrait> FiniteField for RuntimeFp<Integer>{
+    fn legendre_symbol(&self) -> i8{
+        jacobi_symbol(self.repr.clone(), self.modulus().clone())
+    }
+
+    fn exp(a : RuntimeFp<Integer>, n : Integer) -> RuntimeFp<Integer>{
+        if n < Integer::from(0){
+            return RuntimeFp::exp(a.inv(), -n);
+        }
+        if Integer::OVERRIDES_POWM{
+            a.with_repr(a.repr.powm(&n, a.modulus()))
+        }else{
+            let one = a.with_repr(Integer::from(1));
+            square_and_multiply(a, n, one)
+        }
+    }
+
+    // The generic algorithm builds constants with the static constructors, so it runs in the context of self
+    fn square_root(self) -> RuntimeFp<Integer>{
+        let context = self.context.clone();
+        RuntimeFp::with_context(&context, || square_root_mod_p(self))
+    }
+
+    // Same hazard as new and from_int: the modulus of the innermost context entered on the current thread
+    fn cardinal() -> Integer{
+        RuntimeFp::<Integer>::current_context().modulus.clone()
+    }
+
+    fn sign(&self) -> bool{
+        self.repr <= (self.modulus().clone() - Integer::from(1))/Integer::from(2)
+    }
+
+    fn to_integer(&self) -> Integer{
+        self.repr.clone()
+    }
+}
+
+#[cfg(all(test, feature = "gmp"))]
+mod test;
@@ -0,0 +1,115 @@
+use super::*;
+
+use crate::finite_fields::*;
+use crate::elliptic_curves::{EllipticCurve, ProjKPoint};
+use crate::integers::is_probable_prime;
+
+pub type Integer = gmp::mpz::Mpz;
+
+declare_finite_field!(GL8001047, Integer, Integer::from(8001047), m8001047);
+
+fn random_prime_3_mod_4(bits : usize) -> Integer{
+    loop{
+        let p = Integer::sample_uniform(&(Integer::from(1) << (bits - 1)), &(Integer::from(1) << bits));
+        let p = p.clone() - p%4u64 + Integer::from(3);
+        if is_probable_prime(&p){
+            return p;
+        }
+    }
+}
+
+#[test]
+fn matches_compile_time_field(){
+    let context = FpContext::new(Integer::from(8001047));
+    for _i in 0 .. 100{
+        let a = Integer::sample_uniform(&Integer::from(0), &Integer::from(8001047));
+        let b = Integer::sample_uniform(&Integer::from(1), &Integer::from(8001047));
+        let (a_runtime, b_runtime) = (context.element(a.clone()), context.element(b.clone()));
+        let (a_static, b_static) = (GL8001047::new(a.clone()), GL8001047::new(b.clone()));
+
+        assert_eq!((a_runtime.clone() + b_runtime.clone()).to_integer(), (a_static.clone() + b_static.clone()).to_integer());
+        assert_eq!((a_runtime.clone() - b_runtime.clone()).to_integer(), (a_static.clone() - b_static.clone()).to_integer());
+        assert_eq!((a_runtime.clone()*b_runtime.clone()).to_integer(), (a_static.clone()*b_static.clone()).to_integer());
+        assert_eq!((a_runtime.clone()/b_runtime.clone()).to_integer(), (a_static.clone()/b_static.clone()).to_integer());
+        assert_eq!((-a_runtime.clone()).to_integer(), (-a_static.clone()).to_integer());
+        assert_eq!(RuntimeFp::exp(a_runtime.clone(), -b.clone()).to_integer(), GL8001047::exp(a_static.clone(), -b).to_integer());
+        assert_eq!(a_runtime.legendre_symbol(), a_static.legendre_symbol());
+        assert_eq!(a_runtime.sign(), a_static.sign());
+
+        let square = a_runtime.clone()*a_runtime;
+        assert_eq!(square.clone().square_root().to_integer(), (a_static.clone()*a_static).square_root().to_integer());
+    }
+}
+
+#[test]
+fn supersingular_curves_over_random_primes(){
+    // y^2 = x^3 + x has p + 1 points when p = 3 mod 4
+    for _i in 0 .. 5{
+        let p = random_prime_3_mod_4(64);
+        let context = FpContext::new(p.clone());
+        RuntimeFp::with_context(&context, || {
+            assert_eq!(RuntimeFp::<Integer>::cardinal(), p);
+            let ell = EllipticCurve::new_reduced_weierstrass(RuntimeFp::from_int(1), RuntimeFp::from_int(0));
+            let point = ell.sample_point();
+            assert!(ell.is_on_curve(&point));
+            assert_eq!(ell.scalar_mult(p.clone() + Integer::from(1), point), ProjKPoint::InfPoint);
+        });
+    }
+}
+
+// y^2 = x^3 + x has 8012 = 4*2003 points over F_8011. The threads of the walks start without the context of the field
+#[test]
+#[should_panic(expected = "every rho thread stopped")]
+fn parallel_rho_over_a_runtime_field(){
+    let context = FpContext::new(Integer::from(8011));
+    let (ell, p) = RuntimeFp::with_context(&context, || {
+        let ell = EllipticCurve::new_reduced_weierstrass(RuntimeFp::from_int(1), RuntimeFp::from_int(0));
+        loop{
+            let p = ell.scalar_mult(Integer::from(4), ell.sample_point());
+            if p != ProjKPoint::InfPoint{
+                return (ell, p);
+            }
+        }
+    });
+    let parameters = crate::elliptic_curves::discrete_log::parallel_rho::ParallelRhoParameters::new(2, 2);
+    let q = RuntimeFp::with_context(&context, || ell.scalar_mult(Integer::from(5), p.clone()));
+    let _ = RuntimeFp::with_context(&context, || ell.ecdlp_parallel_rho(&p, &q, Integer::from(2003), &parameters));
+}
+
+#[test]
+fn nested_contexts(){
+    let (small, large) = (FpContext::new(Integer::from(1019)), FpContext::new(Integer::from(8001047)));
+    RuntimeFp::with_context(&small, || {
+        let x = RuntimeFp::<Integer>::from_int(-1);
+        RuntimeFp::with_context(&large, || {
+            assert_eq!(RuntimeFp::<Integer>::from_int(-1).to_integer(), Integer::from(8001046));
+            // the elements keep their own field
+            assert_eq!((x.clone()*x.clone()).to_integer(), Integer::from(1));
+            let four = small.element(Integer::from(4));
+            let root = four.clone().square_root();
+            assert_eq!(root.clone()*root, four);
+        });
+        assert_eq!(RuntimeFp::<Integer>::cardinal(), Integer::from(1019));
+    });
+}
+
+#[test]
+#[should_panic(expected = "different runtime fields")]
+fn mixing_fields_panics(){
+    let (small, large) = (FpContext::new(Integer::from(1019)), FpContext::new(Integer::from(8001047)));
+    let _ = small.element(Integer::from(2)) + large.element(Integer::from(2));
+}
+
+#[test]
+#[should_panic(expected = "no runtime field context")]
+fn static_constructor_needs_a_context(){
+    let _ = RuntimeFp::<Integer>::from_int(1);
+}
+
+#[test]
+fn try_inv_with_a_composite_modulus(){
+    let context = FpContext::new(Integer::from(1019*1031));
+    assert!(context.element(Integer::from(1019*5)).try_inv().is_err());
+    let x = context.element(Integer::from(1018));
+    assert_eq!(x.clone().try_inv().unwrap()*x, context.element(Integer::from(1)));
+}
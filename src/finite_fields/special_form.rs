@@ -0,0 +1,228 @@
+use crate::field::IntegerTrait;
+use crate::constant_time::{adc, sbb, mac};
+
+// Reduction modulo p = 2^bits - c for a c much smaller than 2^bits. A pseudo-Mersenne prime folds
+// x = hi*2^bits + lo into hi*c + lo, multiplying hi by the limbs of c. A Solinas prime whose bits and exponents
+// are multiples of 32 is reduced as the NIST primes of FIPS 186-4 D.2: every 32-bit word of x above 2^bits is
+// congruent to a fixed combination with small coefficients of the words below, hence x is reduced by word
+// additions and a final correction. The products are done on the 64-bit limbs of the integers, as for the
+// Montgomery representation
+// The products are computed on the stack, for moduli of up to 1024 bits
+const MAX_LIMBS : usize = 16;
+
+#[derive(Debug)]
+pub struct SpecialFormParameters{
+    modulus : Vec<u64>,
+    bits : usize,
+    fold : Fold,
+}
+
+#[derive(Debug)]
+enum Fold{
+    PseudoMersenne(Vec<u64>), // limbs of c
+    Solinas(Vec<i64>), // column j: the coefficients on the word 2^(32*j) of the words 2^(32*(words + i)) above 2^bits
+}
+
+impl SpecialFormParameters{
+    // p = 2^bits - c
+    pub fn pseudo_mersenne<Integer : IntegerTrait>(modulus : &Integer, bits : usize, c : Integer) -> SpecialFormParameters{
+        assert!((Integer::from(1) << bits) - c.clone() == *modulus, "the modulus is not 2^bits - c");
+        let limbs = crate::integers::bit_length(&c).div_ceil(64);
+        SpecialFormParameters::new(modulus, bits, Fold::PseudoMersenne(c.with_limbs(limbs, |c| c[..limbs].to_vec())))
+    }
+
+    // p = 2^bits - sum of sign*2^exponent over the terms, for bits and exponents multiples of 32
+    pub fn solinas<Integer : IntegerTrait>(modulus : &Integer, bits : usize, terms : &[(i8, usize)]) -> SpecialFormParameters{
+        let mut c = Integer::from(0);
+        for (sign, exponent) in terms{
+            assert!(*sign == 1 || *sign == -1);
+            assert!(exponent.is_multiple_of(32) && *exponent < bits, "the exponents of a Solinas prime must be multiples of 32");
+            c += Integer::from(i32::from(*sign)) << *exponent;
+        }
+        assert!(bits.is_multiple_of(32), "the bits of a Solinas prime must be a multiple of 32");
+        assert!((Integer::from(1) << bits) - c == *modulus, "the modulus is not 2^bits - c");
+
+        // 2^(32*(words + i)) = 2^(32*i)*c, whose words at or above 2^bits are replaced by their own rows
+        let words = bits/32;
+        let mut rows : Vec<Vec<i64>> = Vec::new();
+        for i in 0..words{
+            let mut row = vec![0; words];
+            for (sign, exponent) in terms{
+                let j = i + exponent/32;
+                if j < words{
+                    row[j] += i64::from(*sign);
+                }else{
+                    for (row_k, c_k) in row.iter_mut().zip(&rows[j - words]){
+                        *row_k += i64::from(*sign)*c_k;
+                    }
+                }
+            }
+            rows.push(row);
+        }
+        // the word sums must fit in i64
+        let largest = (0..words).map(|j| rows.iter().map(|row| row[j].abs()).sum::<i64>()).max().unwrap();
+        assert!(largest < 1 << 28, "the coefficients of the Solinas prime are too large");
+        let columns = (0..words).flat_map(|j| rows.iter().map(move |row| row[j])).collect();
+        SpecialFormParameters::new(modulus, bits, Fold::Solinas(columns))
+    }
+
+    fn new<Integer : IntegerTrait>(modulus : &Integer, bits : usize, fold : Fold) -> SpecialFormParameters{
+        // each fold must shrink hi, which needs 0 < c < 2^(bits-1)
+        assert!(*modulus > Integer::from(1) << (bits - 1) && *modulus < Integer::from(1) << bits,
+                "c is too large for a special form reduction");
+        let limbs = bits.div_ceil(64);
+        assert!(limbs <= MAX_LIMBS, "the modulus is too large for a special form reduction");
+        SpecialFormParameters{
+            modulus: modulus.with_limbs(limbs, |p| p[..limbs].to_vec()),
+            bits,
+            fold,
+        }
+    }
+
+    fn limbs(&self) -> usize{
+        self.modulus.len()
+    }
+
+    // a*b mod p, for 0 <= a, b < p
+    pub fn mul<Integer : IntegerTrait>(&self, a : &Integer, b : &Integer) -> Integer{
+        let m = self.limbs();
+        a.with_limbs(m, |a| b.with_limbs(m, |b| Integer::from_limbs_with(m, |result| self.mul_limbs(a, b, result))))
+    }
+
+    fn mul_limbs(&self, a : &[u64], b : &[u64], result : &mut [u64]){
+        let m = self.limbs();
+        // the product on 2m limbs, and one more limb for the carries of the folds
+        let mut buffer = [0; 2*MAX_LIMBS + 1];
+        let x = &mut buffer[..2*m + 1];
+        for (i, a_i) in a[..m].iter().enumerate(){
+            let mut carry = 0;
+            for (x_ij, b_j) in x[i..i + m].iter_mut().zip(&b[..m]){
+                let (t, carry_out) = mac(*x_ij, *a_i, *b_j, carry);
+                *x_ij = t;
+                carry = carry_out;
+            }
+            x[i + m] = carry;
+        }
+        self.reduce_limbs(x);
+        result[..m].copy_from_slice(&x[..m]);
+    }
+
+    // Representative in [0, p) of the non negative x < 2^(2*bits), which must have room for the carries of lo + hi*c
+    fn reduce_limbs(&self, x : &mut [u64]){
+        match &self.fold{
+            Fold::PseudoMersenne(c) => self.fold_limbs(c, x),
+            Fold::Solinas(columns) => self.reduce_words(columns, x),
+        }
+
+        // here x < 2^bits < 2p, and p is subtracted when there is no borrow
+        let m = self.limbs();
+        let mut borrow = 0;
+        for (x_i, p_i) in x[..m].iter().zip(&self.modulus){
+            borrow = sbb(*x_i, *p_i, borrow).1;
+        }
+        if borrow == 0{
+            add_shifted(&mut x[..m], &self.modulus, 0, true);
+        }
+    }
+
+    // x = lo + hi*c until hi = 0
+    fn fold_limbs(&self, c : &[u64], x : &mut [u64]){
+        let (word, shift) = (self.bits/64, self.bits%64);
+        let mut buffer = [0; 2*MAX_LIMBS + 1];
+        let hi = &mut buffer[..x.len() - word];
+        loop{
+            // hi = x >> bits, then x = lo
+            for (i, hi_i) in hi.iter_mut().enumerate(){
+                let next = if shift > 0 && word + i + 1 < x.len() { x[word + i + 1] << (64 - shift) } else { 0 };
+                *hi_i = (x[word + i] >> shift) | next;
+            }
+            let hi = match hi.iter().rposition(|limb| *limb != 0){
+                Some(position) => &hi[..position + 1],
+                None => return,
+            };
+            if shift > 0{
+                x[word] &= (1 << shift) - 1;
+                x[word + 1..].iter_mut().for_each(|limb| *limb = 0);
+            }else{
+                x[word..].iter_mut().for_each(|limb| *limb = 0);
+            }
+
+            for (j, c_j) in c.iter().enumerate(){
+                let mut carry = 0;
+                for (x_ij, hi_i) in x[j..].iter_mut().zip(hi){
+                    let (t, carry_out) = mac(*x_ij, *hi_i, *c_j, carry);
+                    *x_ij = t;
+                    carry = carry_out;
+                }
+                let end = x.len().min(j + hi.len());
+                propagate(&mut x[end..], carry, false);
+            }
+        }
+    }
+
+    // The words of x above 2^bits replaced by their rows, then the signed carry out of 2^bits folded by the row of
+    // 2^bits until it vanishes, which leaves x in [0, 2^bits)
+    fn reduce_words(&self, columns : &[i64], x : &mut [u64]){
+        let words = self.bits/32;
+        let mut buffer = [0i64; 4*MAX_LIMBS];
+        let x_words = &mut buffer[..2*words];
+        for (i, x_i) in x_words.iter_mut().enumerate(){
+            *x_i = ((x[i/2] >> (32*(i%2))) & 0xffff_ffff) as i64;
+        }
+        let (sums, high) = x_words.split_at_mut(words);
+        for (sum, column) in sums.iter_mut().zip(columns.chunks_exact(words)){
+            *sum += column.iter().zip(high.iter()).map(|(c_ij, x_i)| c_ij*x_i).sum::<i64>();
+        }
+        loop{
+            let mut carry = 0;
+            for sum in sums.iter_mut(){
+                let t = *sum + carry;
+                *sum = t & 0xffff_ffff;
+                carry = t >> 32;
+            }
+            if carry == 0{
+                break;
+            }
+            for (sum, column) in sums.iter_mut().zip(columns.chunks_exact(words)){
+                *sum += column[0]*carry;
+            }
+        }
+        x.iter_mut().for_each(|limb| *limb = 0);
+        for (j, sum) in sums.iter().enumerate(){
+            x[j/2] |= (*sum as u64) << (32*(j%2));
+        }
+    }
+}
+
+// x += y*2^shift, or x -= y*2^shift when negative, modulo 2^(64*len(x))
+fn add_shifted(x : &mut [u64], y : &[u64], shift : usize, negative : bool){
+    let (word, shift) = (shift/64, shift%64);
+    let end = x.len().min(word + y.len() + 1);
+    let mut carry = 0;
+    let mut previous = 0;
+    for (i, x_i) in x[word..end].iter_mut().enumerate(){
+        let y_i = y.get(i).copied().unwrap_or(0);
+        let limb = if shift > 0 { (y_i << shift) | (previous >> (64 - shift)) } else { y_i };
+        previous = y_i;
+        let (t, carry_out) = if negative { sbb(*x_i, limb, carry) } else { adc(*x_i, limb, carry) };
+        *x_i = t;
+        carry = carry_out;
+    }
+    propagate(&mut x[end..], carry, negative);
+}
+
+// Carry (or borrow when negative) propagation through x
+fn propagate(x : &mut [u64], carry : u64, negative : bool){
+    let mut carry = carry;
+    for x_i in x.iter_mut(){
+        if carry == 0{
+            return;
+        }
+        let (t, carry_out) = if negative { sbb(*x_i, 0, carry) } else { adc(*x_i, 0, carry) };
+        *x_i = t;
+        carry = carry_out;
+    }
+}
+
+#[cfg(test)]
+mod test;
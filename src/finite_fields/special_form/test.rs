@@ -0,0 +1,147 @@
+use super::*;
+
+#[cfg(feature = "gmp")]
+use gmp::mpz::Mpz;
+
+use crate::finite_fields::*;
+use crate::integers::fixed_width::{Int256, Int320, Int512};
+use crate::standard_curves;
+
+// P-256, P-384, 2^255 - 19 and secp256k1 reduced by folding, by mul_mod and in Montgomery form, on Integer,
+// with WideInteger for P-384
+macro_rules! declare_special_form_fields{
+    ($m: ident, $integer: ty, $wide: ty) => {
+        mod $m{
+            use super::*;
+
+            pub type Integer = $integer;
+            pub type WideInteger = $wide;
+
+            fn p256() -> Integer{
+                (Integer::from(1) << 256) - (Integer::from(1) << 224) + (Integer::from(1) << 192) + (Integer::from(1) << 96) - Integer::from(1)
+            }
+
+            fn p384() -> WideInteger{
+                (WideInteger::from(1) << 384) - (WideInteger::from(1) << 128) - (WideInteger::from(1) << 96)
+                    + (WideInteger::from(1) << 32) - WideInteger::from(1)
+            }
+
+            fn p25519() -> Integer{
+                (Integer::from(1) << 255) - Integer::from(19)
+            }
+
+            fn psecp256k1() -> Integer{
+                (Integer::from(1) << 256) - (Integer::from(1) << 32) - Integer::from(977)
+            }
+
+            crate::declare_finite_field!(SolinasP256, Integer, p256(), msolinasp256, solinas(256, [(1, 224), (-1, 192), (-1, 96), (1, 0)]));
+            crate::declare_finite_field!(DivP256, Integer, p256(), mdivp256);
+            crate::declare_finite_field!(MontP256, Integer, p256(), mmontp256, montgomery);
+            crate::declare_finite_field!(SolinasP384, WideInteger, p384(), msolinasp384, solinas(384, [(1, 128), (1, 96), (-1, 32), (1, 0)]));
+            crate::declare_finite_field!(DivP384, WideInteger, p384(), mdivp384);
+            crate::declare_finite_field!(MontP384, WideInteger, p384(), mmontp384, montgomery);
+            crate::declare_finite_field!(PseudoMersenne25519, Integer, p25519(), mpm25519, pseudo_mersenne(255, Integer::from(19)));
+            crate::declare_finite_field!(Div25519, Integer, p25519(), mdiv25519);
+            crate::declare_finite_field!(Mont25519, Integer, p25519(), mmont25519, montgomery);
+            crate::declare_finite_field!(PseudoMersenneSecp256k1, Integer, psecp256k1(), mpmsecp256k1,
+                                         pseudo_mersenne(256, (Integer::from(1) << 32) + Integer::from(977)));
+            crate::declare_finite_field!(DivSecp256k1, Integer, psecp256k1(), mdivsecp256k1);
+            crate::declare_finite_field!(MontSecp256k1, Integer, psecp256k1(), mmontsecp256k1, montgomery);
+        }
+    };
+}
+
+declare_special_form_fields!(fixed_width_fields, Int320, Int512);
+#[cfg(feature = "gmp")]
+declare_special_form_fields!(mpz_fields, Mpz, Mpz);
+
+// The fields on the integers of the standard curves
+#[cfg(feature = "gmp")]
+use mpz_fields as standard_fields;
+#[cfg(not(feature = "gmp"))]
+use fixed_width_fields as standard_fields;
+
+// The three reductions of a field, on random elements
+fn check_reductions<S : FiniteField, D : FiniteField<Integer = S::Integer>, M : FiniteField<Integer = S::Integer>>(){
+    let p = S::cardinal();
+    for _i in 0..50{
+        let (a, b) = (S::Integer::sample_uniform(&S::Integer::from(1), &p), S::Integer::sample_uniform(&S::Integer::from(0), &p));
+        let product = (S::new(a.clone())*S::new(b.clone())).to_integer();
+        assert!(product == (D::new(a.clone())*D::new(b.clone())).to_integer());
+        assert!(product == (M::new(a.clone())*M::new(b.clone())).to_integer());
+        let exponent = b - S::Integer::from(1);
+        let power = S::exp(S::new(a.clone()), exponent.clone()).to_integer();
+        assert!(power == D::exp(D::new(a.clone()), exponent.clone()).to_integer());
+        assert!(power == M::exp(M::new(a), exponent).to_integer());
+    }
+    // extreme products
+    assert!(S::from_int(-1)*S::from_int(-1) == S::from_int(1));
+    assert!(S::from_int(-1)*S::from_int(2) == S::from_int(-2));
+}
+
+#[test]
+fn special_forms_over_fixed_width(){
+    use fixed_width_fields::*;
+
+    check_reductions::<SolinasP256, DivP256, MontP256>();
+    check_reductions::<SolinasP384, DivP384, MontP384>();
+    check_reductions::<PseudoMersenne25519, Div25519, Mont25519>();
+    check_reductions::<PseudoMersenneSecp256k1, DivSecp256k1, MontSecp256k1>();
+}
+
+#[test]
+#[should_panic(expected = "multiple of 32")]
+fn solinas_needs_words(){
+    let _ = SpecialFormParameters::solinas(&((Int256::from(1) << 127) - Int256::from(1)), 127, &[(1, 0)]);
+}
+
+// Time of count successive squarings
+fn time_squarings<K : FiniteField>(count : usize) -> std::time::Duration{
+    let mut x = K::new(K::Integer::sample_uniform(&K::Integer::from(2), &K::cardinal()));
+    let start = std::time::Instant::now();
+    for _i in 0..count{
+        x = x.clone()*x;
+    }
+    let elapsed = start.elapsed();
+    assert!(x != K::from_int(0));
+    elapsed
+}
+
+// Timings of the products reduced by folding and in Montgomery form, mul_mod being timed too
+fn timings<S : FiniteField, D : FiniteField, M : FiniteField>(name : &str, count : usize) -> (std::time::Duration, std::time::Duration){
+    let (special_form, division, montgomery) = (time_squarings::<S>(count), time_squarings::<D>(count), time_squarings::<M>(count));
+    println!("{}: special form {:?}, mul_mod {:?}, Montgomery {:?}", name, special_form, division, montgomery);
+    (special_form, montgomery)
+}
+
+// The standard field K must reduce its products by the faster of folding and the Montgomery form, up to the noise
+fn check_standard_field<S : FiniteField, D : FiniteField, M : FiniteField, K : FiniteField>(name : &str, count : usize){
+    let (special_form, montgomery) = timings::<S, D, M>(name, count);
+    let standard = time_squarings::<K>(count);
+    println!("{}: standard field {:?}", name, standard);
+    assert!(standard < special_form.min(montgomery)*5/4, "the standard field of {} does not use the faster reduction", name);
+}
+
+// Timings are noisy on shared machines and meaningless in debug builds, hence the benchmark is run on demand only,
+// with cargo test --release special_form_benchmark -- --ignored --nocapture
+#[test]
+#[ignore]
+fn special_form_benchmark(){
+    {
+        use standard_fields::*;
+
+        check_standard_field::<SolinasP256, DivP256, MontP256, standard_curves::FP256>("P-256", 200000);
+        check_standard_field::<SolinasP384, DivP384, MontP384, standard_curves::FP384>("P-384", 200000);
+        check_standard_field::<PseudoMersenne25519, Div25519, Mont25519, standard_curves::F25519>("2^255 - 19", 200000);
+        check_standard_field::<PseudoMersenneSecp256k1, DivSecp256k1, MontSecp256k1, standard_curves::FSecp256k1>("secp256k1", 200000);
+    }
+    #[cfg(feature = "gmp")]
+    {
+        use fixed_width_fields::*;
+
+        timings::<SolinasP256, DivP256, MontP256>("P-256 on FixedInt", 200000);
+        timings::<SolinasP384, DivP384, MontP384>("P-384 on FixedInt", 200000);
+        timings::<PseudoMersenne25519, Div25519, Mont25519>("2^255 - 19 on FixedInt", 200000);
+        timings::<PseudoMersenneSecp256k1, DivSecp256k1, MontSecp256k1>("secp256k1 on FixedInt", 200000);
+    }
+}
@@ -1,11 +1,31 @@
 use super::*;
 
+use crate::field::batch_inv;
+
 pub type Integer = gmp::mpz::Mpz;
 
 declare_finite_field!(GL8001047, Integer, Integer::from(8001047), m8001047);
 declare_finite_field!(GL5483, Integer, Integer::from(5483), m5483);
 declare_finite_field!(GL1009, Integer, Integer::from(1009), m1009); // = 1 mod 8
 declare_finite_field!(GL2, Integer, Integer::from(2), m2);
+declare_finite_field!(Z15, Integer, Integer::from(15), m15);
+declare_finite_field!(MontZ15, Integer, Integer::from(15), mmont15, montgomery);
+declare_finite_field!(MontGL8001047, Integer, Integer::from(8001047), mmont8001047, montgomery);
+declare_finite_field!(MontGL1009, Integer, Integer::from(1009), mmont1009, montgomery);
+declare_finite_field!(MontP256, Integer, (Integer::from(1) << 256) - (Integer::from(1) << 224) + (Integer::from(1) << 192)
+                                         + (Integer::from(1) << 96) - Integer::from(1), mmontp256, montgomery);
+declare_finite_field!(SolinasP256, Integer, (Integer::from(1) << 256) - (Integer::from(1) << 224) + (Integer::from(1) << 192)
+                                            + (Integer::from(1) << 96) - Integer::from(1), msolinasp256,
+                                            solinas(256, [(1, 224), (-1, 192), (-1, 96), (1, 0)]));
+declare_finite_field!(SolinasP384, Integer, (Integer::from(1) << 384) - (Integer::from(1) << 128) - (Integer::from(1) << 96)
+                                            + (Integer::from(1) << 32) - Integer::from(1), msolinasp384,
+                                            solinas(384, [(1, 128), (1, 96), (-1, 32), (1, 0)]));
+declare_finite_field!(DivP384, Integer, (Integer::from(1) << 384) - (Integer::from(1) << 128) - (Integer::from(1) << 96)
+                                        + (Integer::from(1) << 32) - Integer::from(1), mdivp384);
+declare_finite_field!(PseudoMersenne25519, Integer, (Integer::from(1) << 255) - Integer::from(19), mpm25519,
+                      pseudo_mersenne(255, Integer::from(19)));
+declare_finite_field!(PseudoMersenneSecp256k1, Integer, (Integer::from(1) << 256) - (Integer::from(1) << 32) - Integer::from(977),
+                      mpmsecp256k1, pseudo_mersenne(256, (Integer::from(1) << 32) + Integer::from(977)));
 
 #[test]
 fn addition_zero() {
@@ -81,4 +101,105 @@ fn square_root_works(){
         assert!(sq5483 == a_gl5483 || sq5483 == -a_gl5483);
         assert!(sq8001047 == a_gl8001047 || sq8001047 == -a_gl8001047);
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn montgomery_form_matches_division(){
+    let p256 = MontP256::cardinal();
+    for _i in 0 .. 100{
+        let a = Integer::sample_uniform(&Integer::from(0), &Integer::from(8001047));
+        let b = Integer::sample_uniform(&Integer::from(1), &Integer::from(8001047));
+        let (a_mont, b_mont) = (MontGL8001047::new(a.clone()), MontGL8001047::new(b.clone()));
+        let (a_div, b_div) = (GL8001047::new(a.clone()), GL8001047::new(b.clone()));
+
+        assert_eq!(a_mont.to_string(), a.to_string());
+        assert_eq!((a_mont.clone()*b_mont.clone()).to_integer(), (a_div.clone()*b_div.clone()).to_integer());
+        assert_eq!((&a_mont*&b_mont).to_integer(), (&a_div*&b_div).to_integer());
+        assert_eq!((a_mont.clone() + b_mont.clone()).to_integer(), (a_div.clone() + b_div.clone()).to_integer());
+        assert_eq!((a_mont.clone() - b_mont.clone()).to_integer(), (a_div.clone() - b_div.clone()).to_integer());
+        assert_eq!((-a_mont.clone()).to_integer(), (-a_div.clone()).to_integer());
+        assert_eq!((a_mont.clone()/b_mont.clone()).to_integer(), (a_div.clone()/b_div.clone()).to_integer());
+        assert_eq!(MontGL8001047::exp(a_mont.clone(), b.clone()).to_integer(), GL8001047::exp(a_div.clone(), b).to_integer());
+        assert_eq!(a_mont.legendre_symbol(), a_div.legendre_symbol());
+        assert_eq!(a_mont.sign(), a_div.sign());
+        assert_eq!(a_mont.clone() - a_mont.clone(), MontGL8001047::from_int(0));
+
+        let c = MontGL1009::new(a.clone());
+        let sq = (c.clone()*c.clone()).square_root();
+        assert!(sq == c || sq == -c);
+
+        let x = MontP256::new(Integer::sample_uniform(&Integer::from(1), &p256));
+        assert_eq!((x.clone()*x.clone().inv()), MontP256::from_int(1));
+        assert_eq!(MontP256::exp(x.clone(), p256.clone() - Integer::from(1)), MontP256::from_int(1));
+    }
+}
+
+#[test]
+fn special_form_reduction_matches_division(){
+    let p384 = DivP384::cardinal();
+    let p256 = SolinasP256::cardinal();
+    let p25519 = PseudoMersenne25519::cardinal();
+    let psecp256k1 = PseudoMersenneSecp256k1::cardinal();
+    for _i in 0 .. 100{
+        let (a, b) = (Integer::sample_uniform(&Integer::from(0), &p384), Integer::sample_uniform(&Integer::from(0), &p384));
+        assert_eq!((SolinasP384::new(a.clone())*SolinasP384::new(b.clone())).to_integer(), (a.clone()*b.clone())%p384.clone());
+        assert_eq!((&SolinasP384::new(a.clone())*&SolinasP384::new(b.clone())).to_integer(), (&DivP384::new(a.clone())*&DivP384::new(b.clone())).to_integer());
+
+        let (a, b) = (a%p256.clone(), b%p256.clone());
+        assert_eq!((SolinasP256::new(a.clone())*SolinasP256::new(b.clone())).to_integer(), (a.clone()*b.clone())%p256.clone());
+
+        let (a, b) = (a%p25519.clone(), b%p25519.clone());
+        assert_eq!((PseudoMersenne25519::new(a.clone())*PseudoMersenne25519::new(b.clone())).to_integer(), (a.clone()*b.clone())%p25519.clone());
+
+        let x = PseudoMersenneSecp256k1::new(Integer::sample_uniform(&Integer::from(1), &psecp256k1));
+        assert_eq!(x.clone()*x.clone().inv(), PseudoMersenneSecp256k1::from_int(1));
+        let square = x.clone()*x;
+        let root = square.clone().square_root();
+        assert_eq!(root.clone()*root, square);
+    }
+    // extreme products
+    let minus_one = SolinasP384::from_int(-1);
+    assert_eq!(minus_one.clone()*minus_one, SolinasP384::from_int(1));
+    let minus_one = PseudoMersenne25519::from_int(-1);
+    assert_eq!(minus_one.clone()*minus_one, PseudoMersenne25519::from_int(1));
+}
+
+#[test]
+#[should_panic(expected = "not 2^bits - c")]
+fn special_form_checks_the_modulus(){
+    let _ = SpecialFormParameters::pseudo_mersenne(&Integer::from(8001047), 23, Integer::from(19));
+}
+
+#[test]
+fn batch_inv_matches_inv(){
+    let mut elements : Vec<GL8001047> = (0..50).map(|_i| GL8001047::new(Integer::sample_uniform(&Integer::from(0), &Integer::from(8001047)))).collect();
+    elements[3] = GL8001047::from_int(0);
+    elements[17] = GL8001047::from_int(0);
+    let mut inverses = elements.clone();
+    batch_inv(&mut inverses);
+    for (x, x_inv) in elements.into_iter().zip(inverses){
+        if x == GL8001047::from_int(0){
+            assert_eq!(x_inv, GL8001047::from_int(0));
+        }else{
+            assert_eq!(x_inv, GL8001047::from_int(1)/x);
+        }
+    }
+    batch_inv::<GL8001047>(&mut []);
+}
+
+#[test]
+fn try_inv_reports_non_units(){
+    use crate::error::Error;
+
+    assert_eq!(GL8001047::from_int(0).try_inv(), Err(Error::NotInvertible));
+    assert_eq!(GL8001047::from_int(2).try_inv(), Ok(GL8001047::from_int(2).inv()));
+    assert_eq!(GL2::from_int(1).try_inv(), Ok(GL2::from_int(1)));
+    for k in 0..15{
+        let expected = [1, 2, 4, 7, 8, 11, 13, 14].contains(&k);
+        assert_eq!(Z15::from_int(k).try_inv().is_ok(), expected);
+        assert_eq!(MontZ15::from_int(k).try_inv().is_ok(), expected);
+        if let Ok(inverse) = MontZ15::from_int(k).try_inv(){
+            assert_eq!(inverse*MontZ15::from_int(k), MontZ15::from_int(1));
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use crate::field::IntegerTrait;
+
+pub mod fixed_width;
+
+const MILLER_RABIN_ROUNDS : u32 = 25;
+const TRIAL_DIVISION_BOUND : u32 = 1000;
+
+// Representative of a modulo n in [0, n)
+pub fn modulo<Integer : IntegerTrait>(a : Integer, n : &Integer) -> Integer{
+    let r = a%n.clone();
+    if r < Integer::from(0){
+        r + n.clone()
+    }else{
+        r
+    }
+}
+
+// Inverse of a modulo n, None if a and n are not coprime
+pub fn mod_inverse<Integer : IntegerTrait>(a : Integer, n : &Integer) -> Option<Integer>{
+    let (mut t, mut new_t) = (Integer::from(0), Integer::from(1));
+    let (mut r, mut new_r) = (n.clone(), modulo(a, n));
+
+    while new_r != Integer::from(0){
+        let quotient = r.clone() / new_r.clone();
+
+        let old_t = t;
+        t = new_t.clone();
+        new_t = old_t - quotient.clone()*new_t;
+
+        let old_r = r;
+        r = new_r.clone();
+        new_r = old_r - quotient*new_r;
+    }
+    if r != Integer::from(1){
+        return None;
+    }
+    Some(modulo(t, n))
+}
+
+// Floor of the square root of n >= 0, by Newton's method
+pub fn isqrt<Integer : IntegerTrait>(n : &Integer) -> Integer{
+    if n < &Integer::from(2){
+        return n.clone();
+    }
+    let mut x = n.clone();
+    let mut y = (x.clone() + Integer::from(1))/Integer::from(2);
+    while y < x{
+        x = y;
+        y = (x.clone() + n.clone()/x.clone())/Integer::from(2);
+    }
+    x
+}
+
+pub fn gcd<Integer : IntegerTrait>(a : Integer, b : Integer) -> Integer{
+    let (mut a, mut b) = (a, b);
+    while b != Integer::from(0){
+        let r = a%b.clone();
+        a = b;
+        b = r;
+    }
+    if a < Integer::from(0){
+        -a
+    }else{
+        a
+    }
+}
+
+// Jacobi symbol (a/n) for an odd n > 0, which is the Legendre symbol when n is prime
+pub fn jacobi_symbol<Integer : IntegerTrait>(a : Integer, n : Integer) -> i8{
+    let mut a = modulo(a, &n);
+    let mut m = n;
+    let mut t : i8 = 1;
+    while a != Integer::from(0){
+        while a.clone()%2 == Integer::from(0){
+            a >>= 1;
+            if m.clone()%8 == Integer::from(3) || m.clone()%8 == Integer::from(5){
+                t = -t;
+            }
+        }
+        std::mem::swap(&mut a, &mut m);
+
+        if a.clone()%4 == Integer::from(3) && m.clone()%4 == Integer::from(3){
+            t = -t;
+        }
+        a = a%m.clone();
+    }
+    if m == Integer::from(1){
+        t
+    }else{
+        0
+    }
+}
+
+// base^exponent modulo n, for exponent >= 0
+pub fn mod_pow<Integer : IntegerTrait>(base : Integer, exponent : Integer, n : &Integer) -> Integer{
+    let mut result = modulo(Integer::from(1), n);
+    let mut base = modulo(base, n);
+    let mut exponent = exponent;
+    while exponent > Integer::from(0){
+        if exponent.clone()%2 == Integer::from(1){
+            result = result.mul_mod(&base, n);
+        }
+        base = base.mul_mod(&base, n);
+        exponent >>= 1;
+    }
+    result
+}
+
+// Miller-Rabin test with random bases
+pub fn is_probable_prime<Integer : IntegerTrait>(n : &Integer) -> bool{
+    if n < &Integer::from(2){
+        return false;
+    }
+    for small_prime in &[2u32, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37]{
+        if n == &Integer::from(*small_prime){
+            return true;
+        }
+        if n.clone()%u64::from(*small_prime) == Integer::from(0){
+            return false;
+        }
+    }
+
+    let n_minus_one = n.clone() - Integer::from(1);
+    let mut t = n_minus_one.clone();
+    let mut s = 0;
+    while t.clone()%2 == Integer::from(0){ // represent n-1 = t*2^s
+        s += 1;
+        t >>= 1;
+    }
+
+    'witness: for _i in 0..MILLER_RABIN_ROUNDS{
+        let a = Integer::sample_uniform(&Integer::from(2), &n_minus_one);
+        let mut x = a.powm(&t, n);
+        if x == Integer::from(1) || x == n_minus_one{
+            continue;
+        }
+        for _j in 1..s{
+            x = x.mul_mod(&x, n);
+            if x == n_minus_one{
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+// Non trivial factor of the odd composite n, Pollard rho with Floyd cycle detection
+fn pollard_rho_factor<Integer : IntegerTrait>(n : &Integer) -> Integer{
+    loop{
+        let c = Integer::sample_uniform(&Integer::from(1), n);
+        let f = |x : Integer| x.mul_mod(&x, n).add_mod(&c, n);
+        let mut x = Integer::sample_uniform(&Integer::from(0), n);
+        let mut y = x.clone();
+        let mut d = Integer::from(1);
+        while d == Integer::from(1){
+            x = f(x);
+            y = f(f(y));
+            d = gcd(x.clone() - y.clone(), n.clone());
+        }
+        if &d != n{
+            return d;
+        }
+    }
+}
+
+fn factor_into<Integer : IntegerTrait>(n : Integer, factors : &mut Vec<Integer>){
+    if n == Integer::from(1){
+        return;
+    }
+    if is_probable_prime(&n){
+        factors.push(n);
+        return;
+    }
+    let d = pollard_rho_factor(&n);
+    factor_into(n/d.clone(), factors);
+    factor_into(d, factors);
+}
+
+// Prime factorization of n > 0, as increasing primes with their multiplicities
+pub fn factor<Integer : IntegerTrait>(n : &Integer) -> Vec<(Integer, u32)>{
+    assert!(*n > Integer::from(0), "only positive integers are factored");
+    let mut n = n.clone();
+    let mut primes = Vec::new();
+    for d in 2..TRIAL_DIVISION_BOUND{
+        while n.clone()%u64::from(d) == Integer::from(0){
+            primes.push(Integer::from(d));
+            n /= Integer::from(d);
+        }
+    }
+    factor_into(n, &mut primes);
+    primes.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let mut factors : Vec<(Integer, u32)> = Vec::new();
+    for prime in primes{
+        match factors.last_mut(){
+            Some((last, e)) if last == &prime => *e += 1,
+            _ => factors.push((prime, 1)),
+        }
+    }
+    factors
+}
+
+// Chinese remainder theorem: the x in [0, n_1...n_k) with x = r_i mod n_i, for pairwise coprime n_i
+pub fn crt<Integer : IntegerTrait>(congruences : &[(Integer, Integer)]) -> Integer{
+    let mut x = Integer::from(0);
+    let mut modulus = Integer::from(1);
+    for (r, n) in congruences{
+        // x + modulus*k = r mod n
+        let k = (r.clone() - x.clone())*mod_inverse(modulus.clone(), n).expect("CRT moduli must be coprime");
+        x += modulus.clone()*modulo(k, n);
+        modulus *= n.clone();
+    }
+    x
+}
+
+// Integer whose little-endian encoding is bytes
+pub fn from_le_bytes<Integer : IntegerTrait>(bytes : &[u8]) -> Integer{
+    let mut n = Integer::from(0);
+    for byte in bytes.iter().rev(){
+        n = (n << 8) + Integer::from(u32::from(*byte));
+    }
+    n
+}
+
+// Little-endian encoding of 0 <= n < 256^length on length bytes
+pub fn to_le_bytes<Integer : IntegerTrait>(n : &Integer, length : usize) -> Vec<u8>{
+    let mut n = n.clone();
+    (0..length).map(|_| {
+        let byte = (n.clone()%256).to_u64().unwrap() as u8;
+        n >>= 8;
+        byte
+    }).collect()
+}
+
+// Integer whose big-endian encoding is bytes
+pub fn from_be_bytes<Integer : IntegerTrait>(bytes : &[u8]) -> Integer{
+    let mut n = Integer::from(0);
+    for byte in bytes{
+        n = (n << 8) + Integer::from(u32::from(*byte));
+    }
+    n
+}
+
+// Big-endian encoding of 0 <= n < 256^length on length bytes
+pub fn to_be_bytes<Integer : IntegerTrait>(n : &Integer, length : usize) -> Vec<u8>{
+    let mut bytes = to_le_bytes(n, length);
+    bytes.reverse();
+    bytes
+}
+
+// Number of bits of n >= 0
+pub fn bit_length<Integer : IntegerTrait>(n : &Integer) -> usize{
+    let mut length = 0;
+    let mut m = n.clone();
+    while m != Integer::from(0){
+        m >>= 1;
+        length += 1;
+    }
+    length
+}
+
+#[cfg(all(test, feature = "gmp"))]
+mod test;
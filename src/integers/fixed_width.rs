@@ -0,0 +1,668 @@
+use std::ops::{Add, Sub, Mul,
+              Div, Neg, AddAssign,
+              MulAssign, DivAssign,
+              SubAssign, Rem,
+              Shr, ShrAssign,
+              Shl, ShlAssign,
+              BitAnd, BitOr};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::field::IntegerTrait;
+use crate::constant_time::{adc, sbb, mac, mask, select, cswap, MontgomeryDomain};
+
+// Signed integers in two's complement on L little-endian 64-bit limbs, stored on the stack.
+// Operations panic on overflow instead of wrapping, and follow the conventions of Mpz: / and %
+// truncate towards zero, >> rounds towards minus infinity.
+// A prime field modulus must leave two bits free (at most 64L-2 bits) so that sums of reduced elements fit
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FixedInt<const L : usize>{
+    limbs : [u64; L],
+}
+
+pub type Int256 = FixedInt<4>;
+pub type Int320 = FixedInt<5>;
+pub type Int384 = FixedInt<6>;
+pub type Int512 = FixedInt<8>;
+pub type Int1024 = FixedInt<16>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseFixedIntError;
+
+impl fmt::Display for ParseFixedIntError{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid or too large fixed-width integer")
+    }
+}
+
+fn overflow() -> !{
+    panic!("fixed-width integer overflow")
+}
+
+// Unsigned helpers on the limbs
+
+fn unsigned_cmp<const L : usize>(a : &[u64; L], b : &[u64; L]) -> Ordering{
+    a.iter().rev().cmp(b.iter().rev())
+}
+
+fn unsigned_bit_length<const L : usize>(a : &[u64; L]) -> usize{
+    for i in (0..L).rev(){
+        if a[i] != 0{
+            return 64*i + 64 - a[i].leading_zeros() as usize;
+        }
+    }
+    0
+}
+
+// a - b, with the borrow out
+fn unsigned_sub<const L : usize>(a : &[u64; L], b : &[u64; L]) -> ([u64; L], u64){
+    let mut difference = [0; L];
+    let mut borrow = 0;
+    for i in 0..L{
+        let (d, borrow_out) = sbb(a[i], b[i], borrow);
+        difference[i] = d;
+        borrow = borrow_out;
+    }
+    (difference, borrow)
+}
+
+// a << shift and a >> shift, the bits shifted out are lost
+fn unsigned_shl<const L : usize>(a : &[u64; L], shift : usize) -> [u64; L]{
+    let mut result = [0; L];
+    let (limbs, bits) = (shift/64, shift%64);
+    for i in (limbs..L).rev(){
+        result[i] = a[i - limbs] << bits;
+        if bits > 0 && i > limbs{
+            result[i] |= a[i - limbs - 1] >> (64 - bits);
+        }
+    }
+    result
+}
+
+fn unsigned_shr<const L : usize>(a : &[u64; L], shift : usize, fill : u64) -> [u64; L]{
+    let mut result = [fill; L];
+    let (limbs, bits) = (shift/64, shift%64);
+    for i in 0..L.saturating_sub(limbs){
+        result[i] = a[i + limbs] >> bits;
+        let next = if i + limbs + 1 < L{ a[i + limbs + 1] } else { fill };
+        if bits > 0{
+            result[i] |= next << (64 - bits);
+        }
+    }
+    result
+}
+
+// Quotient and remainder of a by a single limb d
+fn unsigned_divrem_limb<const L : usize>(a : &[u64; L], d : u64) -> ([u64; L], u64){
+    let mut quotient = [0; L];
+    let mut remainder = 0u128;
+    for i in (0..L).rev(){
+        let t = (remainder << 64) | a[i] as u128;
+        quotient[i] = (t / d as u128) as u64;
+        remainder = t % d as u128;
+    }
+    (quotient, remainder as u64)
+}
+
+// Quotient and remainder of a by b != 0, by shift and subtract over bit_length(a) - bit_length(b) + 1 steps
+fn unsigned_divrem<const L : usize>(a : &[u64; L], b : &[u64; L]) -> ([u64; L], [u64; L]){
+    if unsigned_cmp(a, b) == Ordering::Less{
+        return ([0; L], *a);
+    }
+    if b[1..].iter().all(|limb| *limb == 0){
+        let (quotient, remainder) = unsigned_divrem_limb(a, b[0]);
+        let mut r = [0; L];
+        r[0] = remainder;
+        return (quotient, r);
+    }
+    let shift = unsigned_bit_length(a) - unsigned_bit_length(b);
+    let mut divisor = unsigned_shl(b, shift);
+    let mut quotient = [0; L];
+    let mut remainder = *a;
+    for i in (0..=shift).rev(){
+        let (difference, borrow) = unsigned_sub(&remainder, &divisor);
+        if borrow == 0{
+            remainder = difference;
+            quotient[i/64] |= 1 << (i%64);
+        }
+        divisor = unsigned_shr(&divisor, 1, 0);
+    }
+    (quotient, remainder)
+}
+
+// a + b mod n for a, b in [0, n), with masks instead of branches
+fn add_mod_limbs<const L : usize>(a : &[u64; L], b : &[u64; L], n : &[u64; L]) -> [u64; L]{
+    let mut sum = [0; L];
+    let mut carry = 0;
+    for (limb, (a_i, b_i)) in sum.iter_mut().zip(a.iter().zip(b)){
+        let (s, c) = adc(*a_i, *b_i, carry);
+        *limb = s;
+        carry = c;
+    }
+    let (difference, borrow) = unsigned_sub(&sum, n);
+    // the sum is below n when subtracting n borrows more than the carry
+    let (_, below) = sbb(carry, 0, borrow);
+    select(mask(below), &sum, &difference)
+}
+
+// a - b mod n for a, b in [0, n), adding back n under a mask when the difference borrows
+fn sub_mod_limbs<const L : usize>(a : &[u64; L], b : &[u64; L], n : &[u64; L]) -> [u64; L]{
+    let (mut difference, borrow) = unsigned_sub(a, b);
+    let addend = mask(borrow);
+    let mut carry = 0;
+    for (limb, n_i) in difference.iter_mut().zip(n){
+        let (s, c) = adc(*limb, addend & n_i, carry);
+        *limb = s;
+        carry = c;
+    }
+    difference
+}
+
+// Schoolbook product of a and b on 2L limbs
+fn unsigned_mul_wide<const L : usize>(a : &[u64; L], b : &[u64; L]) -> [[u64; L]; 2]{
+    let mut product = [[0; L]; 2];
+    let x = product.as_flattened_mut();
+    for (i, a_i) in a.iter().enumerate(){
+        let mut carry = 0;
+        for (x_ij, b_j) in x[i..i + L].iter_mut().zip(b){
+            let (t, carry_out) = mac(*x_ij, *a_i, *b_j, carry);
+            *x_ij = t;
+            carry = carry_out;
+        }
+        x[i + L] = carry;
+    }
+    product
+}
+
+// Remainder of the double-width x by n != 0, by long division on limbs (Knuth's algorithm D)
+fn unsigned_rem_wide<const L : usize>(x : &[[u64; L]; 2], n : &[u64; L]) -> [u64; L]{
+    let t = n.iter().rposition(|limb| *limb != 0).expect("division by zero") + 1;
+    let x = x.as_flattened();
+    let mut remainder = [0; L];
+    if t == 1{
+        let mut r = 0u128;
+        for x_i in x.iter().rev(){
+            r = ((r << 64) | *x_i as u128) % n[0] as u128;
+        }
+        remainder[0] = r as u64;
+        return remainder;
+    }
+
+    // normalization: the divisor v and the dividend u are shifted so that the top bit of v is set
+    let shift = n[t-1].leading_zeros();
+    let v = unsigned_shl(n, shift as usize);
+    let mut buffer = [[0; L]; 3];
+    let u = &mut buffer.as_flattened_mut()[..2*L + 1];
+    for (i, x_i) in x.iter().enumerate(){
+        u[i] |= x_i << shift;
+        if shift > 0{
+            u[i + 1] = x_i >> (64 - shift);
+        }
+    }
+
+    for j in (0..2*L + 1 - t).rev(){
+        // estimate of the quotient digit, at most one too large after the corrections
+        let top = ((u[j + t] as u128) << 64) | u[j + t - 1] as u128;
+        let mut q = top / v[t-1] as u128;
+        let mut r = top % v[t-1] as u128;
+        while q >> 64 != 0 || q*v[t-2] as u128 > ((r << 64) | u[j + t - 2] as u128){
+            q -= 1;
+            r += v[t-1] as u128;
+            if r >> 64 != 0{
+                break;
+            }
+        }
+
+        // u -= q*v at position j, adding v back if it goes below zero
+        let (mut carry, mut borrow) = (0, 0);
+        for (u_i, v_i) in u[j..j + t].iter_mut().zip(&v[..t]){
+            let (product, carry_out) = mac(0, q as u64, *v_i, carry);
+            let (d, borrow_out) = sbb(*u_i, product, borrow);
+            *u_i = d;
+            carry = carry_out;
+            borrow = borrow_out;
+        }
+        let (d, borrow_out) = sbb(u[j + t], carry, borrow);
+        u[j + t] = d;
+        if borrow_out != 0{
+            let mut carry = 0;
+            for (u_i, v_i) in u[j..j + t].iter_mut().zip(&v[..t]){
+                let (s, carry_out) = adc(*u_i, *v_i, carry);
+                *u_i = s;
+                carry = carry_out;
+            }
+            u[j + t] = u[j + t].wrapping_add(carry);
+        }
+    }
+
+    for (i, limb) in remainder[..t].iter_mut().enumerate(){
+        *limb = u[i] >> shift;
+        if shift > 0{
+            *limb |= u[i + 1] << (64 - shift);
+        }
+    }
+    remainder
+}
+
+impl<const L : usize> FixedInt<L>{
+    pub fn from_limbs(limbs : [u64; L]) -> FixedInt<L>{
+        FixedInt{limbs}
+    }
+
+    pub fn limbs(&self) -> &[u64; L]{
+        &self.limbs
+    }
+
+    fn zero() -> FixedInt<L>{
+        FixedInt{limbs: [0; L]}
+    }
+
+    fn is_negative(&self) -> bool{
+        self.limbs[L-1] >> 63 == 1
+    }
+
+    // Two's complement negation, which only wraps for the minimal value
+    fn wrapping_neg(&self) -> FixedInt<L>{
+        let mut limbs = [0; L];
+        let mut carry = 1;
+        for (limb, self_limb) in limbs.iter_mut().zip(&self.limbs){
+            let (s, c) = adc(!self_limb, 0, carry);
+            *limb = s;
+            carry = c;
+        }
+        FixedInt{limbs}
+    }
+
+    // Absolute value as an unsigned number, exact even for the minimal value
+    fn magnitude(&self) -> [u64; L]{
+        if self.is_negative(){
+            self.wrapping_neg().limbs
+        }else{
+            self.limbs
+        }
+    }
+
+    // The integer of absolute value magnitude and sign negative, panics if it is out of range
+    fn from_magnitude(magnitude : [u64; L], negative : bool) -> FixedInt<L>{
+        let n = FixedInt{limbs: magnitude};
+        if !negative{
+            if n.is_negative(){
+                overflow();
+            }
+            return n;
+        }
+        let m = n.wrapping_neg();
+        if magnitude != [0; L] && !m.is_negative(){
+            overflow();
+        }
+        m
+    }
+
+    // Truncated quotient and remainder, the remainder has the sign of self
+    fn divrem(&self, other : &FixedInt<L>) -> (FixedInt<L>, FixedInt<L>){
+        assert!(*other != FixedInt::zero(), "division by zero");
+        let (quotient, remainder) = unsigned_divrem(&self.magnitude(), &other.magnitude());
+        (FixedInt::from_magnitude(quotient, self.is_negative() != other.is_negative()),
+         FixedInt::from_magnitude(remainder, self.is_negative()))
+    }
+
+    pub fn from_str_radix(s : &str, radix : u8) -> Result<FixedInt<L>, ParseFixedIntError>{
+        let (negative, digits) = match s.strip_prefix('-'){
+            Some(digits) => (true, digits),
+            None => (false, s),
+        };
+        if digits.is_empty(){
+            return Err(ParseFixedIntError);
+        }
+        // accumulate the magnitude, checking that it never exceeds the limbs
+        let mut magnitude = [0u64; L];
+        for c in digits.chars(){
+            let digit = c.to_digit(radix as u32).ok_or(ParseFixedIntError)?;
+            let mut carry = digit as u64;
+            for limb in magnitude.iter_mut(){
+                let (s, c) = mac(0, *limb, radix as u64, carry);
+                *limb = s;
+                carry = c;
+            }
+            if carry != 0{
+                return Err(ParseFixedIntError);
+            }
+        }
+        let n = FixedInt{limbs: magnitude};
+        if n.is_negative() && !(negative && n.wrapping_neg() == n){
+            return Err(ParseFixedIntError);
+        }
+        Ok(if negative { n.wrapping_neg() } else { n })
+    }
+}
+
+impl<const L : usize> fmt::Display for FixedInt<L>{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // digits by blocks of 19, the largest power of 10 below 2^64
+        const BLOCK : u64 = 10_000_000_000_000_000_000;
+        let mut magnitude = self.magnitude();
+        let mut blocks = Vec::new();
+        while magnitude != [0; L]{
+            let (quotient, remainder) = unsigned_divrem_limb(&magnitude, BLOCK);
+            blocks.push(remainder);
+            magnitude = quotient;
+        }
+        let mut s = String::new();
+        if self.is_negative(){
+            s.push('-');
+        }
+        match blocks.pop(){
+            None => s.push('0'),
+            Some(block) => s.push_str(&block.to_string()),
+        }
+        for block in blocks.iter().rev(){
+            s.push_str(&format!("{:019}", block));
+        }
+        write!(f, "{}", s)
+    }
+}
+
+impl<const L : usize> FromStr for FixedInt<L>{
+    type Err = ParseFixedIntError;
+
+    fn from_str(s : &str) -> Result<FixedInt<L>, ParseFixedIntError>{
+        FixedInt::from_str_radix(s, 10)
+    }
+}
+
+impl<const L : usize> From<u64> for FixedInt<L>{
+    fn from(n : u64) -> FixedInt<L>{
+        let mut limbs = [0; L];
+        limbs[0] = n;
+        FixedInt::from_magnitude(limbs, false)
+    }
+}
+
+impl<const L : usize> From<u32> for FixedInt<L>{
+    fn from(n : u32) -> FixedInt<L>{
+        FixedInt::from(n as u64)
+    }
+}
+
+impl<const L : usize> From<i32> for FixedInt<L>{
+    fn from(n : i32) -> FixedInt<L>{
+        let mut limbs = [if n < 0 { u64::MAX } else { 0 }; L];
+        limbs[0] = n as i64 as u64;
+        FixedInt{limbs}
+    }
+}
+
+impl<const L : usize> Ord for FixedInt<L>{
+    fn cmp(&self, other : &FixedInt<L>) -> Ordering{
+        match (self.is_negative(), other.is_negative()){
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            _ => unsigned_cmp(&self.limbs, &other.limbs),
+        }
+    }
+}
+
+impl<const L : usize> PartialOrd for FixedInt<L>{
+    fn partial_cmp(&self, other : &FixedInt<L>) -> Option<Ordering>{
+        Some(self.cmp(other))
+    }
+}
+
+impl<const L : usize> Add for FixedInt<L>{
+    type Output = FixedInt<L>;
+
+    fn add(self, other : FixedInt<L>) -> FixedInt<L>{
+        let mut limbs = [0; L];
+        let mut carry = 0;
+        for (limb, (a, b)) in limbs.iter_mut().zip(self.limbs.iter().zip(&other.limbs)){
+            let (s, c) = adc(*a, *b, carry);
+            *limb = s;
+            carry = c;
+        }
+        let sum = FixedInt{limbs};
+        // overflow when both operands have the same sign, different from the sign of the sum
+        if self.is_negative() == other.is_negative() && sum.is_negative() != self.is_negative(){
+            overflow();
+        }
+        sum
+    }
+}
+
+impl<const L : usize> Sub for FixedInt<L>{
+    type Output = FixedInt<L>;
+
+    fn sub(self, other : FixedInt<L>) -> FixedInt<L>{
+        let (limbs, _) = unsigned_sub(&self.limbs, &other.limbs);
+        let difference = FixedInt{limbs};
+        if self.is_negative() != other.is_negative() && difference.is_negative() != self.is_negative(){
+            overflow();
+        }
+        difference
+    }
+}
+
+impl<const L : usize> Mul for FixedInt<L>{
+    type Output = FixedInt<L>;
+
+    fn mul(self, other : FixedInt<L>) -> FixedInt<L>{
+        let (a, b) = (self.magnitude(), other.magnitude());
+        let mut product = [0; L];
+        for i in 0..L{
+            let mut carry = 0;
+            for j in 0..L{
+                if i + j < L{
+                    let (s, c) = mac(product[i+j], a[j], b[i], carry);
+                    product[i+j] = s;
+                    carry = c;
+                }else if a[j] != 0 && b[i] != 0{
+                    overflow();
+                }
+            }
+            if carry != 0{
+                overflow();
+            }
+        }
+        FixedInt::from_magnitude(product, self.is_negative() != other.is_negative())
+    }
+}
+
+impl<const L : usize> Div for FixedInt<L>{
+    type Output = FixedInt<L>;
+
+    fn div(self, other : FixedInt<L>) -> FixedInt<L>{
+        self.divrem(&other).0
+    }
+}
+
+impl<const L : usize> Rem for FixedInt<L>{
+    type Output = FixedInt<L>;
+
+    fn rem(self, other : FixedInt<L>) -> FixedInt<L>{
+        self.divrem(&other).1
+    }
+}
+
+impl<const L : usize> Rem<u64> for FixedInt<L>{
+    type Output = FixedInt<L>;
+
+    fn rem(self, other : u64) -> FixedInt<L>{
+        assert!(other != 0, "division by zero");
+        let (_, remainder) = unsigned_divrem_limb(&self.magnitude(), other);
+        let mut limbs = [0; L];
+        limbs[0] = remainder;
+        FixedInt::from_magnitude(limbs, self.is_negative())
+    }
+}
+
+impl<const L : usize> Neg for FixedInt<L>{
+    type Output = FixedInt<L>;
+
+    fn neg(self) -> FixedInt<L>{
+        FixedInt::from_magnitude(self.magnitude(), !self.is_negative())
+    }
+}
+
+impl<const L : usize> Shl<usize> for FixedInt<L>{
+    type Output = FixedInt<L>;
+
+    fn shl(self, shift : usize) -> FixedInt<L>{
+        if self == FixedInt::zero(){
+            return self;
+        }
+        let shifted = FixedInt{limbs: unsigned_shl(&self.limbs, shift)};
+        if shift >= 64*L || shifted.clone() >> shift != self{
+            overflow();
+        }
+        shifted
+    }
+}
+
+impl<const L : usize> Shr<usize> for FixedInt<L>{
+    type Output = FixedInt<L>;
+
+    fn shr(self, shift : usize) -> FixedInt<L>{
+        let fill = if self.is_negative() { u64::MAX } else { 0 };
+        if shift >= 64*L{
+            return FixedInt{limbs: [fill; L]};
+        }
+        FixedInt{limbs: unsigned_shr(&self.limbs, shift, fill)}
+    }
+}
+
+impl<const L : usize> BitAnd for FixedInt<L>{
+    type Output = FixedInt<L>;
+
+    fn bitand(self, other : FixedInt<L>) -> FixedInt<L>{
+        let mut limbs = self.limbs;
+        for (limb, other_limb) in limbs.iter_mut().zip(&other.limbs){
+            *limb &= other_limb;
+        }
+        FixedInt{limbs}
+    }
+}
+
+impl<const L : usize> BitOr for FixedInt<L>{
+    type Output = FixedInt<L>;
+
+    fn bitor(self, other : FixedInt<L>) -> FixedInt<L>{
+        let mut limbs = self.limbs;
+        for (limb, other_limb) in limbs.iter_mut().zip(&other.limbs){
+            *limb |= other_limb;
+        }
+        FixedInt{limbs}
+    }
+}
+
+impl<const L : usize> AddAssign for FixedInt<L>{
+    fn add_assign(&mut self, other : FixedInt<L>){
+        *self = self.clone() + other;
+    }
+}
+
+impl<const L : usize> SubAssign for FixedInt<L>{
+    fn sub_assign(&mut self, other : FixedInt<L>){
+        *self = self.clone() - other;
+    }
+}
+
+impl<const L : usize> MulAssign for FixedInt<L>{
+    fn mul_assign(&mut self, other : FixedInt<L>){
+        *self = self.clone()*other;
+    }
+}
+
+impl<const L : usize> DivAssign for FixedInt<L>{
+    fn div_assign(&mut self, other : FixedInt<L>){
+        *self = self.clone()/other;
+    }
+}
+
+impl<const L : usize> ShlAssign<usize> for FixedInt<L>{
+    fn shl_assign(&mut self, shift : usize){
+        *self = self.clone() << shift;
+    }
+}
+
+impl<const L : usize> ShrAssign<usize> for FixedInt<L>{
+    fn shr_assign(&mut self, shift : usize){
+        *self = self.clone() >> shift;
+    }
+}
+
+impl<const L : usize> IntegerTrait for FixedInt<L>{
+    fn sample_uniform(min : &FixedInt<L>, max : &FixedInt<L>) -> FixedInt<L>{
+        let mut limbs = [0; L];
+        for limb in limbs.iter_mut(){
+            *limb = rand::random::<u64>();
+        }
+        limbs[L-1] >>= 1;
+        FixedInt{limbs}%(max.clone() - min.clone()) + min.clone()
+    }
+
+    fn to_u64(&self) -> Option<u64>{
+        if self.limbs[1..].iter().all(|limb| *limb == 0) && !self.is_negative(){
+            Some(self.limbs[0])
+        }else{
+            None
+        }
+    }
+
+    fn add_mod(&self, other : &FixedInt<L>, n : &FixedInt<L>) -> FixedInt<L>{
+        FixedInt{limbs: add_mod_limbs(&self.limbs, &other.limbs, &n.limbs)}
+    }
+
+    fn sub_mod(&self, other : &FixedInt<L>, n : &FixedInt<L>) -> FixedInt<L>{
+        FixedInt{limbs: sub_mod_limbs(&self.limbs, &other.limbs, &n.limbs)}
+    }
+
+    // Schoolbook product on 2L limbs reduced by long division, the operands being reduced first
+    fn mul_mod(&self, other : &FixedInt<L>, n : &FixedInt<L>) -> FixedInt<L>{
+        let (a, b) = (crate::integers::modulo(self.clone(), n), crate::integers::modulo(other.clone(), n));
+        FixedInt{limbs: unsigned_rem_wide(&unsigned_mul_wide(&a.limbs, &b.limbs), &n.limbs)}
+    }
+
+    const OVERRIDES_POWM : bool = true;
+
+    // Square and multiply in a Montgomery domain built for this exponentiation, for an odd n
+    fn powm(&self, exponent : &FixedInt<L>, n : &FixedInt<L>) -> FixedInt<L>{
+        let zero = FixedInt::zero();
+        if n.limbs[0] & 1 == 0 || *n == FixedInt::from(1) || *self < zero || self >= n{
+            return crate::integers::mod_pow(self.clone(), exponent.clone(), n);
+        }
+        let domain = MontgomeryDomain::new(&n.limbs);
+        let (mut base, mut result, mut product) = ([0; L], [0; L], [0; L]);
+        domain.to_montgomery(&self.limbs, &mut base);
+        result[..domain.limbs()].copy_from_slice(&domain.one);
+        for i in (0..unsigned_bit_length(&exponent.limbs)).rev(){
+            domain.mul(&result, &result, &mut product);
+            result = product;
+            if (exponent.limbs[i/64] >> (i%64)) & 1 == 1{
+                domain.mul(&result, &base, &mut product);
+                result = product;
+            }
+        }
+        domain.from_montgomery(&result, &mut product);
+        FixedInt{limbs: product}
+    }
+
+    fn conditional_swap(a : &mut FixedInt<L>, b : &mut FixedInt<L>, choice : u64){
+        cswap(mask(choice), &mut a.limbs, &mut b.limbs);
+    }
+
+    // The limbs are used in place, the integer must be non negative
+    fn with_limbs<R, F : FnOnce(&[u64]) -> R>(&self, len : usize, f : F) -> R{
+        assert!(len <= L && !self.is_negative());
+        f(&self.limbs)
+    }
+
+    fn from_limbs_with<F : FnOnce(&mut [u64])>(len : usize, f : F) -> FixedInt<L>{
+        assert!(len <= L);
+        let mut limbs = [0; L];
+        f(&mut limbs);
+        FixedInt::from_magnitude(limbs, false)
+    }
+}
+
+#[cfg(test)]
+mod test;
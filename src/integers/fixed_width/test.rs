@@ -0,0 +1,211 @@
+use super::*;
+
+#[cfg(feature = "gmp")]
+use gmp::mpz::Mpz;
+
+use crate::field::Field;
+use crate::finite_fields::*;
+#[cfg(feature = "gmp")]
+use crate::elliptic_curves::{EllipticCurve, ProjKPoint};
+#[cfg(feature = "gmp")]
+use crate::standard_curves;
+
+type Int = Int512;
+
+#[cfg(feature = "gmp")]
+crate::declare_finite_field!(FSecp256k1, Int320, (Int320::from(1) << 256) - (Int320::from(1) << 32) - Int320::from(977), msecp256k1);
+crate::declare_finite_field!(F25519, Int320, (Int320::from(1) << 255) - Int320::from(19), m25519);
+crate::declare_finite_field!(MontF25519, Int320, (Int320::from(1) << 255) - Int320::from(19), mmont25519, montgomery);
+
+#[cfg(feature = "gmp")]
+fn to_mpz<const L : usize>(n : &FixedInt<L>) -> Mpz{
+    Mpz::from_str_radix(&n.to_string(), 10).unwrap()
+}
+
+#[cfg(feature = "gmp")]
+fn sample_signed() -> Int{
+    let bound = Int::from(1) << (256 + rand::random::<usize>()%200);
+    Int::sample_uniform(&-bound.clone(), &bound)
+}
+
+#[test]
+fn parse_and_display(){
+    for s in &["0", "1", "-1", "18446744073709551616", "-340282366920938463463374607431768211457",
+               "115792089237316195423570985008687907853269984665640564039457584007908834671663"]{
+        assert_eq!(Int::from_str(s).unwrap().to_string(), *s);
+    }
+    assert_eq!(Int::from_str_radix("-ff", 16), Ok(Int::from(-255)));
+    assert!(Int256::from_str("57896044618658097711785492504343953926634992332820282019728792003956564819968").is_err());
+    assert_eq!(Int256::from_str("-57896044618658097711785492504343953926634992332820282019728792003956564819968").unwrap()
+               .to_string(), "-57896044618658097711785492504343953926634992332820282019728792003956564819968");
+    assert!(Int::from_str("12a").is_err());
+}
+
+// Values of i128, on 192 bits to leave room for the products
+fn sample_i128(bits : u32) -> i128{
+    (rand::random::<i128>() >> (128 - bits)).max(-(1 << (bits - 1)) + 1)
+}
+
+fn from_i128(n : i128) -> FixedInt<3>{
+    FixedInt::from_str(&n.to_string()).unwrap()
+}
+
+#[test]
+fn arithmetic_matches_i128(){
+    for _i in 0..2000{
+        let (a, b) = (sample_i128(120), sample_i128(1 + rand::random::<u32>()%120));
+        let (a_int, b_int) = (from_i128(a), from_i128(b));
+        assert_eq!(a_int.clone() + b_int.clone(), from_i128(a + b));
+        assert_eq!(a_int.clone() - b_int.clone(), from_i128(a - b));
+        if b != 0{
+            assert_eq!(a_int.clone()/b_int.clone(), from_i128(a/b));
+            assert_eq!(a_int.clone()%b_int.clone(), from_i128(a%b));
+        }
+        assert_eq!(a_int.clone()%1000003u64, from_i128(a%1000003));
+        assert_eq!(-a_int.clone(), from_i128(-a));
+        assert_eq!(a_int < b_int, a < b);
+        let shift = rand::random::<usize>()%130;
+        assert_eq!(a_int.clone() >> shift, from_i128(a >> shift.min(127)));
+        assert_eq!(a_int.clone() & b_int.clone(), from_i128(a & b));
+        assert_eq!(a_int.clone() | b_int.clone(), from_i128(a | b));
+
+        let (c, d) = (a >> 60, b >> 60);
+        assert_eq!(from_i128(c)*from_i128(d), from_i128(c*d));
+        assert_eq!(from_i128(c) << 60, from_i128(c << 60));
+    }
+}
+
+// Products of operands below 2^64 fit in u128
+#[test]
+fn modular_arithmetic_matches_u128(){
+    for _i in 0..500{
+        let n = rand::random::<u64>() | 1 | (1 << 63);
+        let (a, b, e) = (rand::random::<u64>()%n, rand::random::<u64>()%n, rand::random::<u64>());
+        let (a_int, b_int, n_int) = (Int256::from(a), Int256::from(b), Int256::from(n));
+        let (a, b, n) = (u128::from(a), u128::from(b), u128::from(n));
+        assert_eq!(a_int.add_mod(&b_int, &n_int), Int256::from(((a + b)%n) as u64));
+        assert_eq!(a_int.mul_mod(&b_int, &n_int), Int256::from((a*b%n) as u64));
+
+        let mut power = 1;
+        for i in (0..64).rev(){
+            power = power*power%n;
+            if (e >> i) & 1 == 1{
+                power = power*a%n;
+            }
+        }
+        assert_eq!(a_int.powm(&Int256::from(e), &n_int), Int256::from(power as u64));
+        // even modulus
+        assert_eq!(a_int.mul_mod(&b_int, &(n_int.clone() + Int256::from(1))), Int256::from((a*b%(n + 1)) as u64));
+    }
+}
+
+#[test]
+fn fields_over_fixed_width_agree(){
+    let p = F25519::cardinal();
+    for _i in 0..50{
+        let (a, b, c) = (Int320::sample_uniform(&Int320::from(1), &p), Int320::sample_uniform(&Int320::from(1), &p),
+                         Int320::sample_uniform(&Int320::from(0), &p));
+        let (x, y, z) = (F25519::new(a.clone()), F25519::new(b.clone()), F25519::new(c.clone()));
+        let (x_mont, y_mont) = (MontF25519::new(a), MontF25519::new(b));
+        assert_eq!((x.clone()*y.clone()).to_integer(), (x_mont.clone()*y_mont.clone()).to_integer());
+        assert_eq!((x.clone()/y.clone()).to_integer(), (x_mont.clone()/y_mont).to_integer());
+        assert_eq!((x.clone()*y.clone())*z.clone(), x.clone()*(y.clone()*z.clone()));
+        assert_eq!(x.clone()*(y.clone() + z.clone()), x.clone()*y.clone() + x.clone()*z);
+        assert_eq!(F25519::exp(x.clone(), p.clone() - Int320::from(1)), F25519::from_int(1));
+        assert_eq!(MontF25519::exp(x_mont.clone(), p.clone() - Int320::from(1)), MontF25519::from_int(1));
+        let square = x_mont.clone()*x_mont;
+        let root = square.clone().square_root();
+        assert_eq!(root.clone()*root, square);
+    }
+}
+
+#[cfg(feature = "gmp")]
+#[test]
+fn arithmetic_matches_mpz(){
+    for _i in 0..500{
+        let (a, b) = (sample_signed(), sample_signed());
+        let (a_mpz, b_mpz) = (to_mpz(&a), to_mpz(&b));
+        assert_eq!(to_mpz(&(a.clone() + b.clone())), &a_mpz + &b_mpz);
+        assert_eq!(to_mpz(&(a.clone() - b.clone())), &a_mpz - &b_mpz);
+        assert_eq!(to_mpz(&(a.clone()/b.clone())), &a_mpz/&b_mpz);
+        assert_eq!(to_mpz(&(a.clone()%b.clone())), &a_mpz%&b_mpz);
+        assert_eq!(to_mpz(&(a.clone()%1000003u64)), &a_mpz%1000003u64);
+        assert_eq!(to_mpz(&-a.clone()), -&a_mpz);
+        assert_eq!(a < b, a_mpz < b_mpz);
+        let shift = rand::random::<usize>()%300;
+        assert_eq!(to_mpz(&(a.clone() >> shift)), &a_mpz >> shift);
+        assert_eq!(to_mpz(&(a.clone() & b.clone())), &a_mpz & &b_mpz);
+        assert_eq!(to_mpz(&(a.clone() | b.clone())), &a_mpz | &b_mpz);
+
+        let (c, d) = (a >> 250, b >> 250);
+        assert_eq!(to_mpz(&(c.clone()*d.clone())), to_mpz(&c)*to_mpz(&d));
+        assert_eq!(to_mpz(&(c.clone() << 200)), to_mpz(&c) << 200);
+    }
+}
+
+#[test]
+#[should_panic(expected = "overflow")]
+fn multiplication_overflow_panics(){
+    let a = Int256::from(1) << 200;
+    let _ = a.clone()*a;
+}
+
+#[test]
+#[should_panic(expected = "overflow")]
+fn shift_overflow_panics(){
+    let _ = Int256::from(3) << 254;
+}
+
+#[cfg(feature = "gmp")]
+#[test]
+fn mul_mod_matches_mpz(){
+    let n = Int::from_str("115792089237316195423570985008687907853269984665640564039457584007908834671663").unwrap();
+    for _i in 0..200{
+        let a = Int::sample_uniform(&Int::from(0), &n);
+        let b = Int::sample_uniform(&Int::from(0), &n);
+        assert_eq!(to_mpz(&a.mul_mod(&b, &n)), (to_mpz(&a)*to_mpz(&b))%to_mpz(&n));
+    }
+    // even modulus
+    let m = Int::from(1) << 200;
+    let a = Int::sample_uniform(&Int::from(0), &m);
+    assert_eq!(to_mpz(&a.mul_mod(&a, &m)), (to_mpz(&a)*to_mpz(&a))%to_mpz(&m));
+    // unreduced and negative operands, and moduli whose products exceed the width
+    for _i in 0..200{
+        let (a, b) = (sample_signed(), sample_signed());
+        let m = Int::sample_uniform(&Int::from(1), &(Int::from(1) << (1 + rand::random::<usize>()%510)));
+        let (m_mpz, product) = (to_mpz(&m), to_mpz(&a)*to_mpz(&b));
+        assert_eq!(to_mpz(&a.mul_mod(&b, &m)), ((product%&m_mpz) + &m_mpz)%&m_mpz);
+    }
+}
+
+#[cfg(feature = "gmp")]
+#[test]
+fn field_over_fixed_width(){
+    for _i in 0..50{
+        let a = F25519::new(Int320::sample_uniform(&Int320::from(1), &F25519::cardinal()));
+        let b = F25519::new(Int320::sample_uniform(&Int320::from(1), &F25519::cardinal()));
+        let (a_mpz, b_mpz) = (standard_curves::F25519::new(to_mpz(&a.to_integer())),
+                              standard_curves::F25519::new(to_mpz(&b.to_integer())));
+        assert_eq!((a.clone()*b.clone()).to_string(), (a_mpz.clone()*b_mpz.clone()).to_string());
+        assert_eq!((a.clone()/b.clone()).to_string(), (a_mpz/b_mpz).to_string());
+        let square = a.clone()*a;
+        let root = square.clone().square_root();
+        assert_eq!(root.clone()*root, square);
+    }
+}
+
+#[cfg(feature = "gmp")]
+#[test]
+fn scalar_mult_over_fixed_width(){
+    let params = standard_curves::secp256k1();
+    let ell = EllipticCurve::new_reduced_weierstrass(FSecp256k1::from_int(0), FSecp256k1::from_int(7));
+    let g = match &params.generator{
+        ProjKPoint::FinPoint(x, y) => ProjKPoint::FinPoint(FSecp256k1::new(Int320::from_str(&x.to_string()).unwrap()),
+                                                         FSecp256k1::new(Int320::from_str(&y.to_string()).unwrap())),
+        ProjKPoint::InfPoint => panic!("infinite generator"),
+    };
+    let k = Mpz::from_str_radix("e9873d79c6d87dc0fb6a5778633389f4453213303da61f20bd67fc233aa33262", 16).unwrap();
+    let expected = params.curve.scalar_mult(k.clone(), params.generator.clone());
+    let result = ell.scalar_mult(Int320::from_str(&k.to_string()).unwrap(), g);
+    assert_eq!(result.to_string(), expected.to_string());
+}
@@ -0,0 +1,105 @@
+use super::*;
+
+pub type Integer = gmp::mpz::Mpz;
+
+#[test]
+fn modulo_positive(){
+    assert_eq!(modulo(Integer::from(-3), &Integer::from(7)), Integer::from(4));
+    assert_eq!(modulo(Integer::from(10), &Integer::from(7)), Integer::from(3));
+    assert_eq!(modulo(Integer::from(-14), &Integer::from(7)), Integer::from(0));
+}
+
+#[test]
+fn mod_inverse_works(){
+    let n = Integer::from(8001047);
+    for _i in 0 .. 100{
+        let a = Integer::sample_uniform(&Integer::from(1), &n);
+        let a_inv = mod_inverse(a.clone(), &n).unwrap();
+        assert_eq!((a*a_inv)%n.clone(), Integer::from(1));
+    }
+    assert_eq!(mod_inverse(Integer::from(6), &Integer::from(9)), None);
+}
+
+#[test]
+fn isqrt_works(){
+    for n in 0u64 .. 1000{
+        let r = isqrt(&Integer::from(n));
+        assert!(r.clone()*r.clone() <= Integer::from(n));
+        assert!((r.clone()+Integer::from(1))*(r+Integer::from(1)) > Integer::from(n));
+    }
+}
+
+#[test]
+fn gcd_works(){
+    assert_eq!(gcd(Integer::from(12), Integer::from(18)), Integer::from(6));
+    assert_eq!(gcd(Integer::from(-12), Integer::from(35)), Integer::from(1));
+    assert_eq!(gcd(Integer::from(0), Integer::from(7)), Integer::from(7));
+}
+
+#[test]
+fn mod_pow_works(){
+    let n = Integer::from(8001047);
+    for _i in 0 .. 100{
+        let a = Integer::sample_uniform(&Integer::from(0), &n);
+        let e = Integer::sample_uniform(&Integer::from(0), &n);
+        assert_eq!(mod_pow(a.clone(), e.clone(), &n), a.powm(&e, &n));
+    }
+}
+
+#[test]
+fn primality_works(){
+    let primes : Vec<u32> = (0..2000).filter(|n| *n >= 2 && (2..*n).take_while(|d| d*d <= *n).all(|d| n%d != 0)).collect();
+    for n in 0 .. 2000{
+        assert_eq!(is_probable_prime(&Integer::from(n)), primes.contains(&n));
+    }
+    assert!(is_probable_prime(&Integer::from(8001047)));
+    assert!(!is_probable_prime(&(Integer::from(8001047)*Integer::from(10169))));
+}
+
+#[test]
+fn factor_works(){
+    let n = Integer::from(2*2*2*3*7*7) * Integer::from(10169) * Integer::from(8001047) * Integer::from(8001047);
+    assert_eq!(factor(&n), vec![(Integer::from(2), 3), (Integer::from(3), 1), (Integer::from(7), 2),
+                                (Integer::from(10169), 1), (Integer::from(8001047), 2)]);
+    assert_eq!(factor(&Integer::from(1)), vec![]);
+}
+
+#[test]
+#[should_panic(expected = "only positive integers are factored")]
+fn factor_zero_panics(){
+    factor(&Integer::from(0));
+}
+
+#[test]
+fn crt_works(){
+    let congruences = vec![(Integer::from(2), Integer::from(3)), (Integer::from(3), Integer::from(5)), (Integer::from(2), Integer::from(7))];
+    assert_eq!(crt(&congruences), Integer::from(23));
+}
+
+#[test]
+fn little_endian_encoding(){
+    assert_eq!(from_le_bytes::<Integer>(&[1, 2, 3]), Integer::from(0x030201));
+    assert_eq!(to_le_bytes(&Integer::from(0x030201), 4), vec![1, 2, 3, 0]);
+    for _i in 0 .. 100{
+        let n = Integer::sample_uniform(&Integer::from(0), &(Integer::from(1) << 200));
+        assert_eq!(from_le_bytes::<Integer>(&to_le_bytes(&n, 25)), n);
+    }
+}
+
+#[test]
+fn big_endian_encoding(){
+    assert_eq!(from_be_bytes::<Integer>(&[1, 2, 3]), Integer::from(0x010203));
+    assert_eq!(to_be_bytes(&Integer::from(0x010203), 4), vec![0, 1, 2, 3]);
+    for _i in 0 .. 100{
+        let n = Integer::sample_uniform(&Integer::from(0), &(Integer::from(1) << 200));
+        assert_eq!(from_be_bytes::<Integer>(&to_be_bytes(&n, 25)), n);
+    }
+}
+
+#[test]
+fn bit_length_works(){
+    assert_eq!(bit_length(&Integer::from(0)), 0);
+    assert_eq!(bit_length(&Integer::from(1)), 1);
+    assert_eq!(bit_length(&Integer::from(255)), 8);
+    assert_eq!(bit_length(&(Integer::from(1) << 255)), 256);
+}
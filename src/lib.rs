@@ -1,6 +1,13 @@
-#![feature(specialization)]
-
+pub mod error;
 pub mod field;
+pub mod integers;
 #[macro_use] pub mod finite_fields;
 
-pub mod elliptic_curves;
\ No newline at end of file
+pub mod elliptic_curves;
+
+pub mod standard_curves;
+pub mod ecdh;
+pub mod ecdsa;
+pub mod schnorr;
+pub mod ed25519;
+pub mod constant_time;
\ No newline at end of file
@@ -0,0 +1,165 @@
+use std::convert::TryInto;
+
+use sha2::{Digest, Sha256};
+
+use crate::field::{Field, IntegerTrait};
+use crate::finite_fields::FiniteField;
+use crate::elliptic_curves::ProjKPoint;
+use crate::integers::{modulo, from_be_bytes, to_be_bytes};
+use crate::standard_curves::{DomainParameters, FSecp256k1, Integer, secp256k1};
+
+// BIP-340 Schnorr signatures over secp256k1, with 32-byte x-only public keys
+
+// SHA256(SHA256(tag) || SHA256(tag) || data)
+pub fn tagged_hash(tag : &str, data : &[&[u8]]) -> [u8; 32]{
+    let tag_hash = Sha256::digest(tag.as_bytes());
+    let mut hasher = Sha256::new();
+    hasher.update(tag_hash);
+    hasher.update(tag_hash);
+    for d in data{
+        hasher.update(d);
+    }
+    hasher.finalize().into()
+}
+
+fn has_even_y(point : &ProjKPoint<FSecp256k1>) -> bool{
+    match point{
+        ProjKPoint::FinPoint(_, y) => y.to_integer()%2 == Integer::from(0),
+        ProjKPoint::InfPoint => false,
+    }
+}
+
+fn bytes_x(point : &ProjKPoint<FSecp256k1>) -> [u8; 32]{
+    match point{
+        ProjKPoint::FinPoint(x, _) => to_be_bytes(&x.to_integer(), 32).try_into().unwrap(),
+        ProjKPoint::InfPoint => panic!("the infinite point has no abscissa"),
+    }
+}
+
+// The point of abscissa x with an even ordinate, None if x is not the abscissa of a point
+pub fn lift_x(x : &Integer) -> Option<ProjKPoint<FSecp256k1>>{
+    if x >= &FSecp256k1::cardinal(){
+        return None;
+    }
+    let x = FSecp256k1::new(x.clone());
+    let c = x.clone()*x.clone()*x.clone() + FSecp256k1::from_int(7);
+    if c.legendre_symbol() == -1{
+        return None;
+    }
+    let point = ProjKPoint::FinPoint(x, c.square_root());
+    if has_even_y(&point){
+        Some(point)
+    }else{
+        Some(secp256k1().curve.neg_point(point))
+    }
+}
+
+fn secret_scalar(parameters : &DomainParameters<FSecp256k1>, secret_key : &[u8; 32]) -> Option<Integer>{
+    let d = from_be_bytes(secret_key);
+    if d == Integer::from(0) || d >= parameters.order{
+        None
+    }else{
+        Some(d)
+    }
+}
+
+// x-only public key of a secret key, None if the secret key is not in [1, n-1]
+pub fn public_key(secret_key : &[u8; 32]) -> Option<[u8; 32]>{
+    let parameters = secp256k1();
+    let d = secret_scalar(&parameters, secret_key)?;
+    Some(bytes_x(&parameters.curve.scalar_mult(d, parameters.generator.clone())))
+}
+
+fn challenge(parameters : &DomainParameters<FSecp256k1>, r : &[u8], public_key : &[u8], message : &[u8]) -> Integer{
+    modulo(from_be_bytes(&tagged_hash("BIP0340/challenge", &[r, public_key, message])), &parameters.order)
+}
+
+// Signature of a message with auxiliary random data, None if the secret key is invalid
+pub fn sign(secret_key : &[u8; 32], message : &[u8], aux_rand : &[u8; 32]) -> Option<[u8; 64]>{
+    let parameters = secp256k1();
+    let n = &parameters.order;
+    let ell = &parameters.curve;
+
+    let d = secret_scalar(&parameters, secret_key)?;
+    let p = ell.scalar_mult(d.clone(), parameters.generator.clone());
+    let d = if has_even_y(&p) { d } else { n.clone() - d };
+    let p_bytes = bytes_x(&p);
+
+    let aux_hash = tagged_hash("BIP0340/aux", &[aux_rand]);
+    let t : Vec<u8> = to_be_bytes(&d, 32).iter().zip(aux_hash.iter()).map(|(a, b)| a ^ b).collect();
+    let k = modulo(from_be_bytes(&tagged_hash("BIP0340/nonce", &[&t, &p_bytes, message])), n);
+    if k == Integer::from(0){
+        return None;
+    }
+    let r = ell.scalar_mult(k.clone(), parameters.generator.clone());
+    let k = if has_even_y(&r) { k } else { n.clone() - k };
+    let r_bytes = bytes_x(&r);
+    let e = challenge(&parameters, &r_bytes, &p_bytes, message);
+
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(&r_bytes);
+    signature[32..].copy_from_slice(&to_be_bytes(&k.add_mod(&e.mul_mod(&d, n), n), 32));
+    Some(signature)
+}
+
+// Decode (P, r, s) from a public key and a signature, None if any of them is out of range
+fn parse(parameters : &DomainParameters<FSecp256k1>, public_key : &[u8; 32], signature : &[u8; 64])
+    -> Option<(ProjKPoint<FSecp256k1>, Integer, Integer)>{
+    let p = lift_x(&from_be_bytes(public_key))?;
+    let r = from_be_bytes(&signature[..32]);
+    let s = from_be_bytes(&signature[32..]);
+    if r >= FSecp256k1::cardinal() || s >= parameters.order{
+        return None;
+    }
+    Some((p, r, s))
+}
+
+pub fn verify(public_key : &[u8; 32], message : &[u8], signature : &[u8; 64]) -> bool{
+    let parameters = secp256k1();
+    let ell = &parameters.curve;
+    let (p, r, s) = match parse(&parameters, public_key, signature){
+        Some(parsed) => parsed,
+        None => return false,
+    };
+    let e = challenge(&parameters, &signature[..32], public_key, message);
+
+    // R = [s]G - [e]P
+    let big_r = ell.add_points(ell.scalar_mult(s, parameters.generator.clone()),
+                               ell.scalar_mult(parameters.order.clone() - e, p));
+    match &big_r{
+        ProjKPoint::FinPoint(x, _) => has_even_y(&big_r) && x.to_integer() == r,
+        ProjKPoint::InfPoint => false,
+    }
+}
+
+// Check all the signatures at once, with random weights a_1 = 1, a_2, ..., a_u:
+// [sum a_i s_i]G = sum [a_i]R_i + [a_i e_i]P_i
+pub fn batch_verify(public_keys : &[[u8; 32]], messages : &[&[u8]], signatures : &[[u8; 64]]) -> bool{
+    assert!(public_keys.len() == messages.len() && messages.len() == signatures.len());
+    let parameters = secp256k1();
+    let n = &parameters.order;
+
+    let mut s_sum = Integer::from(0);
+    let mut terms = Vec::new();
+    for (i, ((public_key, message), signature)) in public_keys.iter().zip(messages).zip(signatures).enumerate(){
+        let (p, r, s) = match parse(&parameters, public_key, signature){
+            Some(parsed) => parsed,
+            None => return false,
+        };
+        let big_r = match lift_x(&r){
+            Some(big_r) => big_r,
+            None => return false,
+        };
+        let e = challenge(&parameters, &signature[..32], public_key, message);
+        let a = if i == 0 { Integer::from(1) } else { Integer::sample_uniform(&Integer::from(1), n) };
+
+        s_sum = s_sum.add_mod(&a.mul_mod(&s, n), n);
+        terms.push((a.clone(), big_r));
+        terms.push((a.mul_mod(&e, n), p));
+    }
+    terms.push((-s_sum, parameters.generator.clone()));
+    parameters.curve.multi_scalar_mult(&terms) == ProjKPoint::InfPoint
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,74 @@
+use super::*;
+
+use std::convert::TryInto;
+
+fn bytes<const N : usize>(s : &str) -> [u8; N]{
+    (0..N).map(|i| u8::from_str_radix(&s[2*i..2*i+2], 16).unwrap()).collect::<Vec<u8>>().try_into().unwrap()
+}
+
+// (secret key, public key, aux_rand, message, signature) from the BIP-340 test vectors
+const TEST_VECTORS : [(&str, &str, &str, &str, &str); 4] = [
+    ("0000000000000000000000000000000000000000000000000000000000000003",
+     "F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+     "0000000000000000000000000000000000000000000000000000000000000000",
+     "0000000000000000000000000000000000000000000000000000000000000000",
+     "E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0"),
+    ("B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
+     "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+     "0000000000000000000000000000000000000000000000000000000000000001",
+     "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+     "6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE33418906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A"),
+    ("C90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B14E5C9",
+     "DD308AFEC5777E13121FA72B9CC1B7CC0139715309B086C960E18FD969774EB8",
+     "C87AA53824B4D7AE2EB035A2B5BBBCCC080E76CDC6D1692C4B0B62D798E6D906",
+     "7E2D58D8B3BCDF1ABADEC7829054F90DDA9805AAB56C77333024B9D0A508B75C",
+     "5831AAEED7B44BB74E5EAB94BA9D4294C49BCF2A60728D8B4C200F50DD313C1BAB745879A5AD954A72C45A91C3A51D3C7ADEA98D82F8481E0E1E03674A6F3FB7"),
+    ("0B432B2677937381AEF05BB02A66ECD012773062CF3FA2549E44F58ED2401710",
+     "25D1DFF95105F5253C4022F628A996AD3A0D95FBF21D468A1B33F8C160D8F517",
+     "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+     "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+     "7EB0509757E246F19449885651611CB965ECC1A187DD51B64FDA1EDC9637D5EC97582B9CB13DB3933705B32BA982AF5AF25FD78881EBB32771FC5922EFC66EA3"),
+];
+
+const MESSAGE : &str = "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89";
+
+#[test]
+fn bip340_test_vectors(){
+    for (secret_key, public, aux_rand, message, signature) in TEST_VECTORS.iter(){
+        let secret_key = bytes::<32>(secret_key);
+        let message = bytes::<32>(message);
+        assert_eq!(public_key(&secret_key), Some(bytes(public)));
+        assert_eq!(sign(&secret_key, &message, &bytes(aux_rand)), Some(bytes(signature)));
+        assert!(verify(&bytes(public), &message, &bytes(signature)));
+    }
+}
+
+#[test]
+fn bip340_invalid_signatures(){
+    // public key not on the curve
+    assert!(!verify(&bytes("EEFDEA4CDB677750A420FEE807EACF21EB9898AE79B9768766E4FAA04A2D4A34"), &bytes::<32>(MESSAGE),
+                    &bytes("6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E17776969E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B")));
+    // R has an odd ordinate
+    assert!(!verify(&bytes(TEST_VECTORS[1].1), &bytes::<32>(MESSAGE),
+                    &bytes("FFF97BD5755EEEA420453A14355235D382F6472F8568A18B2F057A14602975563CC27944640AC607CD107AE10923D9EF7A73C643E166BE5EBEAFA34B1AC553E2")));
+
+    let secret_key = bytes::<32>(TEST_VECTORS[1].0);
+    let signature = sign(&secret_key, b"", &[0u8; 32]).unwrap();
+    assert!(verify(&bytes(TEST_VECTORS[1].1), b"", &signature));
+    assert!(!verify(&bytes(TEST_VECTORS[1].1), b"a", &signature));
+
+    assert_eq!(public_key(&[0u8; 32]), None);
+    assert_eq!(lift_x(&(FSecp256k1::cardinal() + Integer::from(1))), None);
+}
+
+#[test]
+fn bip340_batch_verification(){
+    let public_keys : Vec<[u8; 32]> = TEST_VECTORS.iter().map(|v| bytes(v.1)).collect();
+    let messages : Vec<[u8; 32]> = TEST_VECTORS.iter().map(|v| bytes(v.3)).collect();
+    let messages : Vec<&[u8]> = messages.iter().map(|m| &m[..]).collect();
+    let mut signatures : Vec<[u8; 64]> = TEST_VECTORS.iter().map(|v| bytes(v.4)).collect();
+    assert!(batch_verify(&public_keys, &messages, &signatures));
+
+    signatures[2][63] ^= 1;
+    assert!(!batch_verify(&public_keys, &messages, &signatures));
+}
@@ -0,0 +1,101 @@
+use crate::field::Field;
+use crate::finite_fields::*;
+use crate::elliptic_curves::{EllipticCurve, ProjKPoint};
+use crate::elliptic_curves::twisted_edwards::TwistedEdwardsCurve;
+
+// Integers of the 255 and 256-bit fields, and WideInteger of F448 and P-384. The fixed-width ones are sized to
+// the moduli with the two free bits FixedInt asks for, the products being reduced on limbs
+#[cfg(feature = "gmp")]
+pub type Integer = gmp::mpz::Mpz;
+#[cfg(feature = "gmp")]
+pub type WideInteger = gmp::mpz::Mpz;
+#[cfg(not(feature = "gmp"))]
+pub type Integer = crate::integers::fixed_width::Int320;
+#[cfg(not(feature = "gmp"))]
+pub type WideInteger = crate::integers::fixed_width::Int512;
+
+// A curve together with a base point of prime order
+pub struct DomainParameters<K : Field>{
+    pub curve : EllipticCurve<K>,
+    pub generator : ProjKPoint<K>,
+    pub order : K::Integer,
+}
+
+fn hex(s : &str) -> Integer{
+    Integer::from_str_radix(s, 16).unwrap()
+}
+
+fn wide_hex(s : &str) -> WideInteger{
+    WideInteger::from_str_radix(s, 16).unwrap()
+}
+
+fn modulus_25519() -> Integer{
+    (Integer::from(1) << 255) - Integer::from(19)
+}
+
+fn modulus_p256() -> Integer{
+    (Integer::from(1) << 256) - (Integer::from(1) << 224) + (Integer::from(1) << 192) + (Integer::from(1) << 96) - Integer::from(1)
+}
+
+fn modulus_p384() -> WideInteger{
+    (WideInteger::from(1) << 384) - (WideInteger::from(1) << 128) - (WideInteger::from(1) << 96) + (WideInteger::from(1) << 32) - WideInteger::from(1)
+}
+
+fn modulus_secp256k1() -> Integer{
+    (Integer::from(1) << 256) - (Integer::from(1) << 32) - Integer::from(977)
+}
+
+// The products are reduced in Montgomery form, which the special form benchmark measures faster than folding on
+// both FixedInt and Mpz
+declare_finite_field!(F25519, Integer, modulus_25519(), m25519, montgomery);
+declare_finite_field!(FP256, Integer, modulus_p256(), mp256, montgomery);
+declare_finite_field!(FP384, WideInteger, modulus_p384(), mp384, montgomery);
+declare_finite_field!(FSecp256k1, Integer, modulus_secp256k1(), msecp256k1, montgomery);
+declare_finite_field!(F448, WideInteger, (WideInteger::from(1) << 448) - (WideInteger::from(1) << 224) - WideInteger::from(1), m448, montgomery);
+
+// Curve25519: y^2 = x^3 + 486662*x^2 + x over F_(2^255 - 19)
+pub fn curve25519() -> EllipticCurve<F25519>{
+    EllipticCurve::new_montgomery(F25519::from_int(486662))
+}
+
+// Edwards25519: -x^2 + y^2 = 1 - (121665/121666)*x^2*y^2, birational to Curve25519
+pub fn edwards25519() -> TwistedEdwardsCurve<F25519>{
+    TwistedEdwardsCurve::new(F25519::from_int(-1), -F25519::from_int(121665)/F25519::from_int(121666))
+}
+
+// Curve448: y^2 = x^3 + 156326*x^2 + x over F_(2^448 - 2^224 - 1)
+pub fn curve448() -> EllipticCurve<F448>{
+    EllipticCurve::new_montgomery(F448::from_int(156326))
+}
+
+// NIST P-256: y^2 = x^3 - 3*x + b over F_(2^256 - 2^224 + 2^192 + 2^96 - 1)
+pub fn p256() -> DomainParameters<FP256>{
+    DomainParameters{
+        curve: EllipticCurve::new_reduced_weierstrass(FP256::from_int(-3),
+            FP256::new(hex("5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b"))),
+        generator: ProjKPoint::FinPoint(FP256::new(hex("6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296")),
+                                        FP256::new(hex("4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5"))),
+        order: hex("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551"),
+    }
+}
+
+// NIST P-384: y^2 = x^3 - 3*x + b over F_(2^384 - 2^128 - 2^96 + 2^32 - 1)
+pub fn p384() -> DomainParameters<FP384>{
+    DomainParameters{
+        curve: EllipticCurve::new_reduced_weierstrass(FP384::from_int(-3),
+            FP384::new(wide_hex("b3312fa7e23ee7e4988e056be3f82d19181d9c6efe8141120314088f5013875ac656398d8a2ed19d2a85c8edd3ec2aef"))),
+        generator: ProjKPoint::FinPoint(FP384::new(wide_hex("aa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b9859f741e082542a385502f25dbf55296c3a545e3872760ab7")),
+                                        FP384::new(wide_hex("3617de4a96262c6f5d9e98bf9292dc29f8f41dbd289a147ce9da3113b5f0b8c00a60b1ce1d7e819d7a431d7c90ea0e5f"))),
+        order: wide_hex("ffffffffffffffffffffffffffffffffffffffffffffffffc7634d81f4372ddf581a0db248b0a77aecec196accc52973"),
+    }
+}
+
+// secp256k1: y^2 = x^3 + 7 over F_(2^256 - 2^32 - 977)
+pub fn secp256k1() -> DomainParameters<FSecp256k1>{
+    DomainParameters{
+        curve: EllipticCurve::new_reduced_weierstrass(FSecp256k1::from_int(0), FSecp256k1::from_int(7)),
+        generator: ProjKPoint::FinPoint(FSecp256k1::new(hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")),
+                                        FSecp256k1::new(hex("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"))),
+        order: hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"),
+    }
+}